@@ -0,0 +1,31 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    ffi::build();
+}
+
+/// Generates the C header; the linkable libraries are built by the `ffi/` crate.
+#[cfg(feature = "ffi")]
+mod ffi {
+    use std::{env, path::PathBuf};
+
+    pub fn build() {
+        let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+
+        let config = cbindgen::Config {
+            usize_is_size_t: true,
+            ..Default::default()
+        };
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(crate_dir.join("src/ffi.rs"))
+            .with_language(cbindgen::Language::C)
+            .with_cpp_compat(true)
+            .with_include_guard("OPENSKILL_H")
+            .with_autogen_warning("/* Generated by cbindgen from src/ffi.rs, do not edit. */")
+            .generate()
+            .expect("failed to generate the C header")
+            .write_to_file(out_dir.join("openskill.h"));
+    }
+}
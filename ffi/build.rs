@@ -0,0 +1,7 @@
+fn main() {
+    // the C test program is compiled for the same target as the library it links
+    println!(
+        "cargo:rustc-env=OPENSKILL_FFI_TARGET={}",
+        std::env::var("TARGET").unwrap()
+    );
+}
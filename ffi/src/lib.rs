@@ -0,0 +1,5 @@
+//! `libopenskill_ffi.a` and `libopenskill_ffi.so` (or the platform equivalents) exporting the
+//! functions of `openskill::ffi`, declared in `include/openskill.h`. This is a separate crate
+//! so that `openskill` itself stays an rlib that `no_std` users can depend on.
+
+pub use openskill::ffi::*;
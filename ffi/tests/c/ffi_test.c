@@ -0,0 +1,80 @@
+/* Exercises the C ABI through the shared library; built and run by tests/c_abi.rs. */
+#include <math.h>
+#include <stddef.h>
+#include <stdio.h>
+
+#include "openskill.h"
+
+#define CHECK(cond)                                                    \
+    do {                                                               \
+        if (!(cond)) {                                                 \
+            fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #cond); \
+            return 1;                                                  \
+        }                                                              \
+    } while (0)
+
+static int close_to(double a, double b) { return fabs(a - b) < 1e-9; }
+
+int main(void) {
+    OpenSkillEnv *env = NULL;
+    CHECK(openskill_env_new(NULL, &env) == OPENSKILL_OK);
+    CHECK(env != NULL);
+
+    OpenSkillConfig config = openskill_config_default();
+    CHECK(close_to(config.mu, 25.0));
+
+    /* 1v1 between default players, team 0 wins */
+    double mu[] = {25.0, 25.0};
+    double sigma[] = {25.0 / 3.0, 25.0 / 3.0};
+    size_t offsets[] = {0, 1, 2};
+    size_t ranks[] = {1, 2};
+    double out_mu[2], out_sigma[2];
+    CHECK(openskill_rate(env, mu, sigma, offsets, 2, ranks, out_mu, out_sigma) == OPENSKILL_OK);
+    CHECK(close_to(out_mu[0], 27.63523138347365));
+    CHECK(close_to(out_mu[1], 22.36476861652635));
+    CHECK(close_to(out_sigma[0], 8.065506316323548));
+    CHECK(close_to(out_sigma[1], 8.065506316323548));
+
+    /* null ranks means finishing order */
+    double again_mu[2], again_sigma[2];
+    CHECK(openskill_rate(env, mu, sigma, offsets, 2, NULL, again_mu, again_sigma) == OPENSKILL_OK);
+    CHECK(close_to(again_mu[0], out_mu[0]));
+
+    double win[2];
+    CHECK(openskill_predict_win(env, mu, sigma, offsets, 2, win) == OPENSKILL_OK);
+    CHECK(close_to(win[0], 0.5) && close_to(win[1], 0.5));
+
+    double draw = 0.0;
+    CHECK(openskill_predict_draw(env, mu, sigma, offsets, 2, &draw) == OPENSKILL_OK);
+    CHECK(draw > 0.0 && draw < 1.0);
+
+    double ordinal[2];
+    CHECK(openskill_ordinal(env, mu, sigma, 2, ordinal) == OPENSKILL_OK);
+    CHECK(close_to(ordinal[0], 0.0));
+
+    /* errors */
+    size_t empty_offsets[] = {0, 0, 1};
+    CHECK(openskill_rate(env, mu, sigma, empty_offsets, 2, ranks, out_mu, out_sigma) ==
+          OPENSKILL_ERROR_EMPTY_TEAMS);
+    CHECK(openskill_predict_win(env, mu, sigma, empty_offsets, 2, win) ==
+          OPENSKILL_ERROR_INVALID_TEAM_COUNT);
+    size_t bad_offsets[] = {0, 2, 1};
+    CHECK(openskill_predict_win(env, mu, sigma, bad_offsets, 2, win) == OPENSKILL_ERROR_INVALID_ARGUMENT);
+    CHECK(openskill_predict_win(NULL, mu, sigma, offsets, 2, win) == OPENSKILL_ERROR_INVALID_ARGUMENT);
+    CHECK(openskill_error_message(OPENSKILL_ERROR_EMPTY_TEAMS) != NULL);
+    CHECK(openskill_error_message(OPENSKILL_ERROR_PANIC) != NULL);
+    openskill_env_free(env);
+
+    config.model = 42;
+    env = NULL;
+    CHECK(openskill_env_new(&config, &env) == OPENSKILL_ERROR_INVALID_ARGUMENT);
+    CHECK(env == NULL);
+    openskill_env_free(NULL);
+
+    config.model = OPENSKILL_MODEL_THURSTONE_MOSTELLER_FULL;
+    CHECK(openskill_env_new(&config, &env) == OPENSKILL_OK);
+    CHECK(openskill_rate(env, mu, sigma, offsets, 2, ranks, out_mu, out_sigma) == OPENSKILL_OK);
+    CHECK(out_mu[0] > 25.0 && out_mu[1] < 25.0);
+    openskill_env_free(env);
+    return 0;
+}
@@ -0,0 +1,46 @@
+//! Builds `tests/c/ffi_test.c` against the shared library of this crate and runs it.
+#![cfg(unix)]
+
+use std::{env, path::PathBuf, process::Command};
+
+#[test]
+fn c_program_links_and_passes() {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // target/<profile>/deps/c_abi-* -> target/<profile>, where the libraries are
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap().parent().unwrap();
+    let out_dir = env::temp_dir().join(format!("openskill_ffi_test_{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let program = out_dir.join("ffi_test");
+
+    let target = env!("OPENSKILL_FFI_TARGET");
+    let compiler = cc::Build::new()
+        .target(target)
+        .host(target)
+        .opt_level(0)
+        .cargo_metadata(false)
+        .get_compiler();
+    let status = compiler
+        .to_command()
+        .arg(manifest.join("tests/c/ffi_test.c"))
+        .arg("-I")
+        .arg(manifest.join("../include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lopenskill_ffi")
+        .arg("-lm")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling the C test program failed");
+
+    let output = Command::new(&program).output().unwrap();
+    std::fs::remove_dir_all(&out_dir).unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
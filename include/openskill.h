@@ -0,0 +1,137 @@
+#ifndef OPENSKILL_H
+#define OPENSKILL_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define OPENSKILL_OK 0
+
+#define OPENSKILL_ERROR_EMPTY_TEAMS 1
+
+#define OPENSKILL_ERROR_INVALID_TEAM_COUNT 2
+
+#define OPENSKILL_ERROR_INVALID_ARGUMENT 3
+
+#define OPENSKILL_ERROR_PANIC 4
+
+#define OPENSKILL_MODEL_PLACKETT_LUCE 0
+
+#define OPENSKILL_MODEL_BRADLEY_TERRY_PART 1
+
+#define OPENSKILL_MODEL_BRADLEY_TERRY_FULL 2
+
+#define OPENSKILL_MODEL_THURSTONE_MOSTELLER_PART 3
+
+#define OPENSKILL_MODEL_THURSTONE_MOSTELLER_FULL 4
+
+/**
+ * Opaque handle created by `openskill_env_new` and released by `openskill_env_free`.
+ */
+typedef struct OpenSkillEnv OpenSkillEnv;
+
+typedef struct OpenSkillConfig {
+  /**
+   * One of the `OPENSKILL_MODEL_*` constants.
+   */
+  int model;
+  double mu;
+  double sigma;
+  double beta;
+  double kappa;
+  double z;
+} OpenSkillConfig;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Default configuration, to be adjusted before `openskill_env_new`.
+ */
+struct OpenSkillConfig openskill_config_default(void);
+
+/**
+ * Returns a static, NUL terminated description of an `OPENSKILL_*` code.
+ */
+const char *openskill_error_message(int code);
+
+/**
+ * Creates an env from `config`, or from `openskill_config_default()` when it is null.
+ *
+ * # Safety
+ * `config` must be null or valid, `out` must be valid for writes.
+ */
+int openskill_env_new(const struct OpenSkillConfig *config, struct OpenSkillEnv **out);
+
+/**
+ * Releases an env; null is ignored.
+ *
+ * # Safety
+ * `env` must be null or come from `openskill_env_new`, and is not usable afterwards.
+ */
+void openskill_env_free(struct OpenSkillEnv *env);
+
+/**
+ * Rates one game. `ranks` holds `team_count` ranks (lower is better) or is null for
+ * finishing order; `out_mu`/`out_sigma` receive one entry per player.
+ *
+ * # Safety
+ * Every non-null pointer must be valid for the lengths described above.
+ */
+int openskill_rate(const struct OpenSkillEnv *env,
+                   const double *mu,
+                   const double *sigma,
+                   const size_t *offsets,
+                   size_t team_count,
+                   const size_t *ranks,
+                   double *out_mu,
+                   double *out_sigma);
+
+/**
+ * Writes the win probability of each of the `team_count` teams to `out`.
+ *
+ * # Safety
+ * Every pointer must be valid for the lengths implied by `offsets` and `team_count`.
+ */
+int openskill_predict_win(const struct OpenSkillEnv *env,
+                          const double *mu,
+                          const double *sigma,
+                          const size_t *offsets,
+                          size_t team_count,
+                          double *out);
+
+/**
+ * Writes the probability that all `team_count` teams draw to `out`.
+ *
+ * # Safety
+ * Every pointer must be valid for the lengths implied by `offsets` and `team_count`.
+ */
+int openskill_predict_draw(const struct OpenSkillEnv *env,
+                           const double *mu,
+                           const double *sigma,
+                           const size_t *offsets,
+                           size_t team_count,
+                           double *out);
+
+/**
+ * Writes the ordinal of each of the `count` ratings to `out`.
+ *
+ * # Safety
+ * `mu`, `sigma` and `out` must be valid for `count` elements.
+ */
+int openskill_ordinal(const struct OpenSkillEnv *env,
+                      const double *mu,
+                      const double *sigma,
+                      size_t count,
+                      double *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* OPENSKILL_H */
@@ -0,0 +1,5 @@
+//! The `openskill_rs` Python extension module, i.e. `openskill::python`, built by maturin
+//! from `pyproject.toml`. This is a separate crate so that `openskill` itself stays an rlib
+//! that `no_std` users can depend on.
+
+pub use openskill::python::*;
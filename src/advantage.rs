@@ -0,0 +1,236 @@
+use crate::{
+    math::{sigma_bar, v, w},
+    rating::{apply_advantage, to_team_ratings, AggregateFunc, GameResult},
+};
+use alloc::{vec, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-9;
+
+/// Estimates a per team slot advantage from past games by maximizing the pairwise
+/// Thurstone-Mosteller likelihood of the recorded ranks, using the ratings stored in each
+/// `GameResult` as the pre-game ratings. Advantages are shrunk toward zero by a `N(0, beta²)`
+/// prior, which also keeps them summing to zero, so slots without evidence stay at 0.
+pub fn learn_advantage(
+    history: &[GameResult],
+    beta: f64,
+    slots: usize,
+    aggregate: AggregateFunc,
+) -> Vec<f64> {
+    let beta_sq = beta.powi(2);
+    let mut advantage = vec![0.0; slots];
+    if slots < 2 {
+        return advantage;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut grad: Vec<f64> = advantage.iter().map(|a| -a / beta_sq).collect();
+        let mut hess: Vec<Vec<f64>> = (0..slots)
+            .map(|i| {
+                (0..slots)
+                    .map(|j| if i == j { 1.0 / beta_sq } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+        for result in history {
+            let mut teams = to_team_ratings(&result.teams, Some(result.ranks.clone()), aggregate);
+            apply_advantage(&mut teams, &advantage);
+            let n = teams.len().min(slots);
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let (team_i, team_j) = (&teams[i], &teams[j]);
+                    if team_i.rank == team_j.rank {
+                        continue;
+                    }
+                    let sign = if team_i.rank < team_j.rank { 1.0 } else { -1.0 };
+                    let c = sigma_bar(
+                        team_i.sigma_sq,
+                        team_j.sigma_sq,
+                        beta_sq,
+                        team_i.team_size() + team_j.team_size(),
+                    );
+                    let x = sign * (team_i.mu - team_j.mu) / c;
+                    let g = result.weight * v(x, 0.0) / c;
+                    let h = result.weight * w(x, 0.0) / c.powi(2);
+                    grad[i] += sign * g;
+                    grad[j] -= sign * g;
+                    hess[i][i] += h;
+                    hess[j][j] += h;
+                    hess[i][j] -= h;
+                    hess[j][i] -= h;
+                }
+            }
+        }
+
+        let step = solve(hess, grad);
+        let mut max_step: f64 = 0.0;
+        for (a, s) in advantage.iter_mut().zip(step) {
+            *a += s;
+            max_step = max_step.max(s.abs());
+        }
+        if max_step < TOLERANCE {
+            break;
+        }
+    }
+    advantage
+}
+
+// Gaussian elimination with partial pivoting; `a` is positive definite thanks to the prior.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))
+            .unwrap_or(col);
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in (col + 1)..n {
+            let factor = a[row][col] / a[col][col];
+            let pivot_row = a[col].clone();
+            for (x, p) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *x -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum = ((row + 1)..n).map(|k| a[row][k] * x[k]).sum::<f64>();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    x
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        constant::DEFAULT_BETA,
+        env::test::for_each_model,
+        env_builder::EnvBuilder,
+        error::OpenSkillError,
+        math::normal,
+        rating::{sum_aggregate, Rating},
+    };
+    use approx::relative_eq;
+
+    fn history(first_wins: usize, games: usize) -> Vec<GameResult> {
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+        (0..games)
+            .map(|g| {
+                let ranks = if g < first_wins {
+                    vec![1, 2]
+                } else {
+                    vec![2, 1]
+                };
+                GameResult::new(teams.clone(), ranks)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn no_history_has_no_advantage() {
+        assert_eq!(
+            learn_advantage(&[], DEFAULT_BETA, 2, sum_aggregate),
+            vec![0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn balanced_history_has_no_advantage() {
+        let got = learn_advantage(&history(500, 1000), DEFAULT_BETA, 2, sum_aggregate);
+        assert!(relative_eq!(got[0], 0.0, epsilon = 1e-9));
+        assert!(relative_eq!(got[1], 0.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn recovers_first_mover_advantage() {
+        let edge = 2.0;
+        let rating = Rating::default();
+        let c = sigma_bar(
+            rating.sigma.powi(2),
+            rating.sigma.powi(2),
+            DEFAULT_BETA.powi(2),
+            2,
+        );
+        let games = 5000;
+        let first_wins = (normal().cdf(edge / c) * games as f64).round() as usize;
+        let got = learn_advantage(&history(first_wins, games), DEFAULT_BETA, 2, sum_aggregate);
+        assert!(relative_eq!(got[0] + got[1], 0.0, epsilon = 1e-9));
+        assert!(
+            relative_eq!(got[0] - got[1], edge, epsilon = 0.05),
+            "got: {got:?}"
+        );
+    }
+
+    #[test]
+    fn advantage_acts_as_shifted_team_mu() {
+        let edge = 1.5;
+        let teams = vec![vec![Rating::new(24.0, 6.0)], vec![Rating::new(27.0, 5.0)]];
+        let shifted = vec![
+            vec![Rating::new(24.0 + edge, 6.0)],
+            vec![Rating::new(27.0, 5.0)],
+        ];
+        for_each_model(|builder| {
+            let env = builder.clone().advantage(vec![edge]).build();
+            let plain = builder.build();
+
+            let got = env
+                .rate(&GameResult::new(teams.clone(), vec![1, 2]))
+                .unwrap();
+            let want = plain
+                .rate(&GameResult::new(shifted.clone(), vec![1, 2]))
+                .unwrap();
+            assert!(relative_eq!(
+                got[0][0].mu + edge,
+                want[0][0].mu,
+                epsilon = 1e-9
+            ));
+            assert!(relative_eq!(got[1][0].mu, want[1][0].mu, epsilon = 1e-9));
+            assert!(relative_eq!(
+                got[0][0].sigma,
+                want[0][0].sigma,
+                epsilon = 1e-9
+            ));
+
+            let overridden = env
+                .rate(&GameResult::new(teams.clone(), vec![1, 2]).advantage(vec![0.0, 0.0]))
+                .unwrap();
+            let unshifted = plain
+                .rate(&GameResult::new(teams.clone(), vec![1, 2]))
+                .unwrap();
+            assert_eq!(overridden[0][0].mu, unshifted[0][0].mu);
+
+            let win = env.predict_win(&teams).unwrap();
+            let want_win = plain.predict_win(&shifted).unwrap();
+            assert!(relative_eq!(win[0], want_win[0], epsilon = 1e-12));
+            let draw = env.predict_draw(&teams).unwrap();
+            let want_draw = plain.predict_draw(&shifted).unwrap();
+            assert!(relative_eq!(draw, want_draw, epsilon = 1e-12));
+        });
+    }
+
+    #[test]
+    fn advantage_and_ranks_need_one_entry_per_team() {
+        let env = EnvBuilder::default().build();
+        let teams = vec![vec![Rating::new(24.0, 6.0)], vec![Rating::new(27.0, 5.0)]];
+        let short = GameResult::new(teams.clone(), vec![1, 2]).advantage(vec![1.0]);
+        assert_eq!(
+            env.rate(&short).unwrap_err(),
+            OpenSkillError::InvalidArgument("advantage must be empty or have one entry per team")
+        );
+        let long = GameResult::new(teams.clone(), vec![1, 2]).advantage(vec![1.0, 0.0, 0.0]);
+        assert!(env.rate(&long).is_err());
+
+        for ranks in [vec![1], vec![1, 2, 3]] {
+            assert_eq!(
+                env.rate(&GameResult::new(teams.clone(), ranks))
+                    .unwrap_err(),
+                OpenSkillError::InvalidArgument("ranks must have one entry per team")
+            );
+        }
+    }
+}
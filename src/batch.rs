@@ -0,0 +1,211 @@
+use crate::{
+    error::Result,
+    rating::{GameResult, Rating},
+    smooth::TimedResult,
+    validate::validate_match,
+};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use num_traits::Float;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+type Rated<F> = Result<Vec<Vec<Rating<F>>>>;
+
+/// Maps every item, on the rayon thread pool with the `rayon` feature.
+pub(crate) fn par_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    return items.par_iter().map(f).collect();
+    #[cfg(not(feature = "rayon"))]
+    return items.iter().map(f).collect();
+}
+
+/// Splits `matches` into waves that can each be rated at once: matches are taken in order of
+/// `time` (ties keep their order in the slice) and every match lands one wave after the last
+/// match of any of its players, so no player is in two matches of the same wave.
+pub(crate) fn waves<K: Ord>(matches: &[TimedResult<K>]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..matches.len()).collect();
+    order.sort_by(|a, b| matches[*a].time.total_cmp(&matches[*b].time));
+
+    let mut last: BTreeMap<&K, usize> = BTreeMap::new();
+    let mut waves: Vec<Vec<usize>> = Vec::new();
+    for i in order {
+        let players = matches[i].teams.iter().flatten();
+        let wave = players
+            .clone()
+            .filter_map(|id| last.get(id))
+            .map(|it| it + 1)
+            .max()
+            .unwrap_or(0);
+        for id in players {
+            last.insert(id, wave);
+        }
+        if wave == waves.len() {
+            waves.push(vec![]);
+        }
+        waves[wave].push(i);
+    }
+    waves
+}
+
+/// Rates `matches` wave by wave with `rate_batch`, reading and updating `ratings`. Unknown
+/// players start at `default`; a match that fails leaves the ratings of its players as they were.
+pub(crate) fn rate_scheduled<K: Ord + Clone, F: Float>(
+    matches: &[TimedResult<K>],
+    ratings: &mut BTreeMap<K, Rating<F>>,
+    default: &Rating<F>,
+    rate_batch: impl Fn(&[GameResult<F>]) -> Vec<Rated<F>>,
+) -> Vec<Rated<F>> {
+    let mut out: Vec<Option<Rated<F>>> = matches.iter().map(|_| None).collect();
+    for wave in waves(matches) {
+        let mut rated = Vec::with_capacity(wave.len());
+        let mut games = Vec::with_capacity(wave.len());
+        for i in wave {
+            match game(&matches[i], ratings, default) {
+                Ok(game) => {
+                    rated.push(i);
+                    games.push(game);
+                }
+                Err(err) => out[i] = Some(Err(err)),
+            }
+        }
+
+        for (i, result) in rated.into_iter().zip(rate_batch(&games)) {
+            if let Ok(teams) = &result {
+                let players = matches[i].teams.iter().flatten();
+                for (id, rating) in players.zip(teams.iter().flatten()) {
+                    ratings.insert(id.clone(), rating.clone());
+                }
+            }
+            out[i] = Some(result);
+        }
+    }
+    out.into_iter().flatten().collect()
+}
+
+fn game<K: Ord, F: Float>(
+    result: &TimedResult<K>,
+    ratings: &BTreeMap<K, Rating<F>>,
+    default: &Rating<F>,
+) -> Result<GameResult<F>> {
+    validate_match(&result.teams, &result.ranks)?;
+    let teams = result
+        .teams
+        .iter()
+        .map(|team| {
+            team.iter()
+                .map(|id| ratings.get(id).unwrap_or(default).clone())
+                .collect()
+        })
+        .collect();
+    Ok(GameResult::new(teams, result.ranks.clone()).weight(result.weight))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{env::Env, env_builder::EnvBuilder, error::OpenSkillError};
+
+    fn matches() -> Vec<TimedResult<&'static str>> {
+        vec![
+            TimedResult::new(2.0, vec![vec!["alice"], vec!["carol"]], vec![2, 1]),
+            TimedResult::new(1.0, vec![vec!["alice"], vec!["bob"]], vec![1, 2]),
+            TimedResult::new(1.0, vec![vec!["carol", "dave"], vec!["erin"]], vec![1, 2]),
+            TimedResult::new(3.0, vec![vec!["frank"], vec!["gina"]], vec![1, 1]).weight(0.5),
+            TimedResult::new(
+                4.0,
+                vec![vec!["bob"], vec!["dave"], vec!["erin"]],
+                vec![3, 1, 2],
+            ),
+        ]
+    }
+
+    /// Plain one at a time replay of `matches` in order of time.
+    fn sequential(
+        env: &Env,
+        matches: &[TimedResult<&'static str>],
+    ) -> BTreeMap<&'static str, Rating> {
+        let mut ordered: Vec<_> = matches.iter().collect();
+        ordered.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let mut ratings = BTreeMap::new();
+        for it in ordered {
+            let game = game(it, &ratings, &env.new_rating()).unwrap();
+            let rated = env.rate(&game).unwrap();
+            for (id, rating) in it.teams.iter().flatten().zip(rated.iter().flatten()) {
+                ratings.insert(*id, rating.clone());
+            }
+        }
+        ratings
+    }
+
+    #[test]
+    fn waves_keep_shared_players_apart() {
+        assert_eq!(waves(&matches()), vec![vec![1, 2, 3], vec![0, 4]]);
+        assert!(waves::<u32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn rate_batch_matches_rate() {
+        let env = EnvBuilder::default().build();
+        let games: Vec<GameResult> = matches()
+            .iter()
+            .map(|it| game(it, &BTreeMap::new(), &env.new_rating()).unwrap())
+            .chain([GameResult::new(vec![vec![], vec![]], vec![1, 2])])
+            .collect();
+        let got = env.rate_batch(&games);
+        assert_eq!(got.len(), games.len());
+        for (got, game) in got.iter().zip(&games) {
+            match (got, env.rate(game)) {
+                (Ok(got), Ok(want)) => {
+                    for (got, want) in got.iter().flatten().zip(want.iter().flatten()) {
+                        assert_eq!((got.mu, got.sigma), (want.mu, want.sigma));
+                    }
+                }
+                (Err(got), Err(want)) => assert_eq!(got, &want),
+                _ => panic!("rate_batch and rate disagree"),
+            }
+        }
+    }
+
+    #[test]
+    fn rate_scheduled_matches_sequential() {
+        let env = EnvBuilder::default().build();
+        let mut got = BTreeMap::new();
+        let results = env.rate_scheduled(&matches(), &mut got);
+        assert!(results.iter().all(Result::is_ok));
+
+        let want = sequential(&env, &matches());
+        assert_eq!(
+            got.keys().collect::<Vec<_>>(),
+            want.keys().collect::<Vec<_>>()
+        );
+        for (id, rating) in &want {
+            assert_eq!(
+                (got[id].mu, got[id].sigma),
+                (rating.mu, rating.sigma),
+                "{id}"
+            );
+        }
+        // results come back in the order of the input, not of time
+        let carol = &results[0].as_ref().unwrap()[1][0];
+        assert_eq!(carol.mu, got["carol"].mu);
+    }
+
+    #[test]
+    fn failed_matches_leave_ratings_alone() {
+        let env = EnvBuilder::default().build();
+        let mut ratings = BTreeMap::from([("alice", Rating::new(30.0, 5.0))]);
+        let matches = [
+            TimedResult::new(1.0, vec![vec!["alice"], vec!["alice"]], vec![1, 2]),
+            TimedResult::new(2.0, vec![vec!["alice"], vec!["bob"]], vec![1]),
+        ];
+        let results = env.rate_scheduled(&matches, &mut ratings);
+        assert_eq!(
+            results[0].as_ref().unwrap_err(),
+            &OpenSkillError::InvalidArgument("a player can only appear once per match")
+        );
+        assert!(results[1].is_err());
+        assert_eq!(ratings.len(), 1);
+        assert_eq!(ratings["alice"].mu, 30.0);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::store::Store;
+use openskill::{
+    env::Env,
+    error::OpenSkillError,
+    leaderboard::Leaderboard,
+    rating::{GameResult, Rating},
+    validate::validate_match,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io;
+
+const DEFAULT_LIMIT: usize = 100;
+
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    pub fn error(status: u16, message: String) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+}
+
+enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    Internal(String),
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::BadRequest(err.to_string())
+    }
+}
+
+impl From<OpenSkillError> for ApiError {
+    fn from(err: OpenSkillError) -> Self {
+        Self::BadRequest(err.to_string())
+    }
+}
+
+impl From<io::Error> for ApiError {
+    fn from(err: io::Error) -> Self {
+        Self::Internal(err.to_string())
+    }
+}
+
+impl From<ApiError> for Response {
+    fn from(err: ApiError) -> Self {
+        match err {
+            ApiError::BadRequest(message) => Self::error(400, message),
+            ApiError::NotFound(message) => Self::error(404, message),
+            ApiError::Internal(message) => Self::error(500, message),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatchRequest {
+    teams: Vec<Vec<String>>,
+    /// Lower is better; defaults to the order of `teams`.
+    ranks: Option<Vec<usize>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PredictRequest {
+    teams: Vec<Vec<String>>,
+}
+
+/// Routes:
+/// - `POST /matches` `{"teams": [["a", "b"], ["c"]], "ranks": [1, 2]}` rates and stores a match
+/// - `GET /players/{id}`
+/// - `POST /predict/win` and `POST /predict/draw` `{"teams": [["a"], ["b"]]}`
+/// - `GET /leaderboard?limit=100&offset=0` players by ordinal, best first
+///
+/// Players that were never rated use the env's default rating.
+pub struct Api {
+    env: Env,
+    store: Store,
+    board: Leaderboard<String>,
+}
+
+impl Api {
+    pub fn new(env: Env, store: Store) -> Self {
+        let mut board = env.leaderboard();
+        for (id, player) in store.players() {
+            board.update(id.clone(), &player.rating, false);
+        }
+        Self { env, store, board }
+    }
+
+    pub fn handle(&mut self, method: &str, url: &str, body: &str) -> Response {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let result = match (method, path) {
+            ("POST", "/matches") => self.rate(body),
+            ("POST", "/predict/win") => self.predict_win(body),
+            ("POST", "/predict/draw") => self.predict_draw(body),
+            ("GET", "/leaderboard") => self.leaderboard(query),
+            ("GET", _) if path.starts_with("/players/") => {
+                self.player(&percent_decode(&path["/players/".len()..]))
+            }
+            _ => Err(ApiError::NotFound(format!("no route for {method} {path}"))),
+        };
+        match result {
+            Ok(body) => Response { status: 200, body },
+            Err(err) => err.into(),
+        }
+    }
+
+    fn rating(&self, id: &str) -> Rating {
+        self.store
+            .get(id)
+            .map_or_else(|| self.env.new_rating(), |it| it.rating.clone())
+    }
+
+    fn teams(&self, ids: &[Vec<String>]) -> Vec<Vec<Rating>> {
+        ids.iter()
+            .map(|team| team.iter().map(|id| self.rating(id)).collect())
+            .collect()
+    }
+
+    fn player_json(&self, id: &str) -> Value {
+        let rating = self.rating(id);
+        json!({
+            "id": id,
+            "mu": rating.mu,
+            "sigma": rating.sigma,
+            "ordinal": self.env.ordinal(&rating),
+            "games": self.store.get(id).map_or(0, |it| it.games),
+        })
+    }
+
+    fn rate(&mut self, body: &str) -> Result<Value, ApiError> {
+        let request: MatchRequest = serde_json::from_str(body)?;
+        if request.teams.len() < 2 {
+            return Err(OpenSkillError::InvalidTeamCount("game must have atleast 2 teams").into());
+        }
+        let ranks = request
+            .ranks
+            .unwrap_or_else(|| (1..=request.teams.len()).collect());
+        validate_match(&request.teams, &ranks)?;
+
+        let rated = self
+            .env
+            .rate(&GameResult::new(self.teams(&request.teams), ranks))?;
+        let updates: Vec<(String, Rating)> = request
+            .teams
+            .iter()
+            .flatten()
+            .cloned()
+            .zip(rated.into_iter().flatten())
+            .collect();
+        self.store.record(&updates)?;
+        for (id, rating) in updates {
+            self.board.update(id, &rating, false);
+        }
+
+        let teams: Vec<Vec<Value>> = request
+            .teams
+            .iter()
+            .map(|team| team.iter().map(|id| self.player_json(id)).collect())
+            .collect();
+        Ok(json!({ "teams": teams }))
+    }
+
+    fn player(&self, id: &str) -> Result<Value, ApiError> {
+        match self.store.get(id) {
+            Some(_) => Ok(self.player_json(id)),
+            None => Err(ApiError::NotFound(format!("unknown player {id}"))),
+        }
+    }
+
+    fn predict_win(&self, body: &str) -> Result<Value, ApiError> {
+        let request: PredictRequest = serde_json::from_str(body)?;
+        let probabilities = self.env.predict_win(&self.teams(&request.teams))?;
+        Ok(json!({ "probabilities": probabilities }))
+    }
+
+    fn predict_draw(&self, body: &str) -> Result<Value, ApiError> {
+        let request: PredictRequest = serde_json::from_str(body)?;
+        let probability = self.env.predict_draw(&self.teams(&request.teams))?;
+        Ok(json!({ "probability": probability }))
+    }
+
+    fn leaderboard(&self, query: &str) -> Result<Value, ApiError> {
+        let mut limit = DEFAULT_LIMIT;
+        let mut offset = 0;
+        for (key, value) in query.split('&').filter_map(|it| it.split_once('=')) {
+            let value = value
+                .parse()
+                .map_err(|_| ApiError::BadRequest(format!("{key} must be a number")))?;
+            match key {
+                "limit" => limit = value,
+                "offset" => offset = value,
+                _ => return Err(ApiError::BadRequest(format!("unknown parameter {key}"))),
+            }
+        }
+
+        let page: Vec<Value> = self
+            .board
+            .range(offset, limit)
+            .into_iter()
+            .map(|standing| {
+                let mut player = self.player_json(&standing.id);
+                player["rank"] = json!(standing.rank);
+                player
+            })
+            .collect();
+        Ok(json!({ "total": self.board.len(), "players": page }))
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|it| std::str::from_utf8(it).ok())
+            .and_then(|it| u8::from_str_radix(it, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use openskill::env_builder::EnvBuilder;
+
+    fn api() -> Api {
+        Api::new(EnvBuilder::default().build(), Store::in_memory())
+    }
+
+    #[test]
+    fn rated_matches_show_up_everywhere() {
+        let mut api = api();
+        let rated = api.handle(
+            "POST",
+            "/matches",
+            r#"{"teams": [["alice"], ["bob", "carol dean"]], "ranks": [1, 2]}"#,
+        );
+        assert_eq!(rated.status, 200, "{}", rated.body);
+        assert!(rated.body["teams"][0][0]["mu"].as_f64().unwrap() > 25.0);
+
+        let alice = api.handle("GET", "/players/alice", "");
+        assert_eq!(alice.status, 200);
+        assert_eq!(alice.body["games"], 1);
+        assert_eq!(alice.body, rated.body["teams"][0][0]);
+        assert_eq!(api.handle("GET", "/players/carol%20dean", "").status, 200);
+        assert_eq!(api.handle("GET", "/players/dave", "").status, 404);
+
+        let board = api.handle("GET", "/leaderboard?limit=2&offset=1", "");
+        assert_eq!(board.body["total"], 3);
+        let players = board.body["players"].as_array().unwrap();
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0]["rank"], 2);
+        assert_eq!(
+            api.handle("GET", "/leaderboard", "").body["players"][0]["id"],
+            "alice"
+        );
+
+        // a restarted server ranks the players it loads
+        let before = api.handle("GET", "/leaderboard", "").body;
+        let Api { env, store, .. } = api;
+        let mut reloaded = Api::new(env, store);
+        assert_eq!(reloaded.handle("GET", "/leaderboard", "").body, before);
+    }
+
+    #[test]
+    fn predictions_use_stored_ratings() {
+        let mut api = api();
+        let body = r#"{"teams": [["alice"], ["bob"]]}"#;
+        let even = api.handle("POST", "/predict/win", body);
+        assert_eq!(even.body["probabilities"], json!([0.5, 0.5]));
+
+        api.handle("POST", "/matches", r#"{"teams": [["alice"], ["bob"]]}"#);
+        let win = api.handle("POST", "/predict/win", body);
+        assert!(win.body["probabilities"][0].as_f64().unwrap() > 0.5);
+        let draw = api.handle("POST", "/predict/draw", body);
+        let p = draw.body["probability"].as_f64().unwrap();
+        assert!(p > 0.0 && p < 1.0);
+    }
+
+    #[test]
+    fn bad_requests_are_rejected() {
+        let mut api = api();
+        let cases = [
+            ("POST", "/matches", r#"{"teams": [["a"], ["a"]]}"#),
+            (
+                "POST",
+                "/matches",
+                r#"{"teams": [["a"], ["b"]], "ranks": [1]}"#,
+            ),
+            ("POST", "/matches", r#"{"teams": [["a"], []]}"#),
+            ("POST", "/matches", r#"{"teams": [["a", "b"]]}"#),
+            ("POST", "/predict/win", "not json"),
+            ("GET", "/leaderboard?limit=many", ""),
+        ];
+        for (method, url, body) in cases {
+            let response = api.handle(method, url, body);
+            assert_eq!(response.status, 400, "{url} {body}");
+            assert!(response.body["error"].is_string());
+        }
+        assert_eq!(api.handle("DELETE", "/matches", "").status, 404);
+        assert_eq!(api.store.players().count(), 0);
+    }
+}
@@ -0,0 +1,72 @@
+use openskill::{env_builder::EnvBuilder, model::kind::ModelKind};
+use serde::Deserialize;
+use std::{error::Error, fs, path::PathBuf};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+/// Server options read from a JSON file; every field is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Listen address, `127.0.0.1:8080` by default.
+    pub addr: Option<String>,
+    /// JSON file the players are loaded from and saved to; in memory only when unset.
+    pub store: Option<PathBuf>,
+    pub model: Option<ModelKind>,
+    pub mu: Option<f64>,
+    pub sigma: Option<f64>,
+    pub beta: Option<f64>,
+    pub kappa: Option<f64>,
+    pub z: Option<f64>,
+}
+
+impl Config {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn addr(&self) -> &str {
+        self.addr.as_deref().unwrap_or(DEFAULT_ADDR)
+    }
+
+    pub fn env_builder(&self) -> EnvBuilder {
+        let mut builder = EnvBuilder::default();
+        if let Some(model) = &self.model {
+            builder = builder.model(model.clone());
+        }
+        for (value, set) in [
+            (self.mu, EnvBuilder::mu as fn(EnvBuilder, f64) -> EnvBuilder),
+            (self.sigma, EnvBuilder::sigma),
+            (self.beta, EnvBuilder::beta),
+            (self.kappa, EnvBuilder::kappa),
+            (self.z, EnvBuilder::z),
+        ] {
+            if let Some(value) = value {
+                builder = set(builder, value);
+            }
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn config_feeds_env_builder() {
+        let config: Config =
+            serde_json::from_str(r#"{"model": "ThurstoneMostellerFull", "mu": 1500.0, "z": 2.0}"#)
+                .unwrap();
+        let env = config.env_builder().build();
+        assert_eq!(env.new_rating().mu, 1500.0);
+        assert_eq!(env.new_rating().sigma, 750.0);
+        assert_eq!(config.addr(), DEFAULT_ADDR);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        assert!(serde_json::from_str::<Config>(r#"{"mu": 25.0, "tau": 1.0}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"model": "Glicko"}"#).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+//! Reference JSON HTTP rating service around `Env`.
+//!
+//! `openskill-server [config.json]`, see `Config` for the options and `Api` for the routes.
+
+mod api;
+mod config;
+mod store;
+
+use api::Api;
+use config::Config;
+use std::error::Error;
+use store::Store;
+use tiny_http::{Header, Response, Server};
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let config = match std::env::args().nth(1) {
+        Some(path) => Config::load(&path)?,
+        None => Config::default(),
+    };
+    let store = match &config.store {
+        Some(path) => Store::open(path.clone())?,
+        None => Store::in_memory(),
+    };
+    let mut api = Api::new(config.env_builder().build(), store);
+
+    let server = Server::http(config.addr())?;
+    println!("listening on http://{}", config.addr());
+    let json = Header::from_bytes("Content-Type", "application/json").unwrap();
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let response = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => api.handle(request.method().as_str(), request.url(), &body),
+            Err(err) => api::Response::error(400, err.to_string()),
+        };
+        let response = Response::from_string(response.body.to_string())
+            .with_status_code(response.status)
+            .with_header(json.clone());
+        if let Err(err) = request.respond(response) {
+            eprintln!("failed to respond: {err}");
+        }
+    }
+    Ok(())
+}
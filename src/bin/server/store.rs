@@ -0,0 +1,128 @@
+use openskill::rating::Rating;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Player {
+    #[serde(flatten)]
+    pub rating: Rating,
+    pub games: usize,
+}
+
+/// Players by id, optionally mirrored to a JSON file after every change.
+#[derive(Debug, Default)]
+pub struct Store {
+    path: Option<PathBuf>,
+    players: BTreeMap<String, Player>,
+}
+
+impl Store {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Loads `path` if it exists, otherwise starts empty and creates it on the first save.
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let players = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            path: Some(path),
+            players,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Player> {
+        self.players.get(id)
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = (&String, &Player)> {
+        self.players.iter()
+    }
+
+    /// Stores the new rating of every player of a match and counts the game. Nothing changes
+    /// if the store can't be saved.
+    pub fn record(&mut self, ratings: &[(String, Rating)]) -> io::Result<()> {
+        let mut previous = Vec::with_capacity(ratings.len());
+        for (id, rating) in ratings {
+            let games = self.players.get(id).map_or(0, |it| it.games);
+            let player = Player {
+                rating: rating.clone(),
+                games: games + 1,
+            };
+            previous.push((id, self.players.insert(id.clone(), player)));
+        }
+        let saved = self.save();
+        if saved.is_err() {
+            for (id, player) in previous.into_iter().rev() {
+                match player {
+                    Some(player) => self.players.insert(id.clone(), player),
+                    None => self.players.remove(id),
+                };
+            }
+        }
+        saved
+    }
+
+    // write then rename, so a crash never leaves a half written store behind
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&self.players)?)?;
+        fs::rename(tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn file_store_round_trips() {
+        let path =
+            std::env::temp_dir().join(format!("openskill-store-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut store = Store::open(path.clone()).unwrap();
+        assert!(store.get("alice").is_none());
+        store
+            .record(&[("alice".to_string(), Rating::new(30.0, 5.0))])
+            .unwrap();
+        store
+            .record(&[("alice".to_string(), Rating::new(31.0, 4.0))])
+            .unwrap();
+
+        let reopened = Store::open(path.clone()).unwrap();
+        let alice = reopened.get("alice").unwrap();
+        assert_eq!(alice.rating.mu, 31.0);
+        assert_eq!(alice.games, 2);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn failed_save_changes_nothing() {
+        let path = std::env::temp_dir()
+            .join(format!("openskill-missing-{}", std::process::id()))
+            .join("store.json");
+        let mut store = Store::open(path).unwrap();
+        store.players.insert(
+            "bob".to_string(),
+            Player {
+                rating: Rating::new(20.0, 4.0),
+                games: 3,
+            },
+        );
+        let rated = [
+            ("alice".to_string(), Rating::new(30.0, 5.0)),
+            ("bob".to_string(), Rating::new(21.0, 3.0)),
+        ];
+        assert!(store.record(&rated).is_err());
+        assert!(store.get("alice").is_none());
+        let bob = store.get("bob").unwrap();
+        assert_eq!((bob.rating.mu, bob.games), (20.0, 3));
+    }
+}
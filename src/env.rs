@@ -1,19 +1,50 @@
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use core::marker::PhantomData;
+use num_traits::Float;
+
 use crate::{
+    advantage::learn_advantage,
+    batch::{par_map, rate_scheduled},
+    constant::KAPPA,
     env_builder::EnvBuilder,
     error::{OpenSkillError, Result},
+    explain::TeamExplanation,
+    import::EloImport,
+    items::ItemRanking,
+    leaderboard::Leaderboard,
     model::model::Model,
-    predict_draw::predict_draw,
-    predict_win::predict_win,
-    rating::{GameResult, OrdinalFunc, Rating},
+    predict_draw::{draw_matrix_teams, predict_draw_teams},
+    predict_outcome::{predict_outcome_teams, OutcomeProbability},
+    predict_win::{predict_win_teams, win_matrix_teams},
+    preview::{changes, outcome_ranks, TeamPreview},
+    provisional::{games_until_established, rate_placement, ProvisionalPolicy, RatingStatus},
+    rating::{
+        apply_advantage, cast, cast_teams, sum_aggregate, to_team_ratings, AggregateFunc,
+        GameResult, OrdinalFunc, Rating, TeamRating,
+    },
+    scale::{RatingScale, RatingScaleBuilder},
+    smooth::{smooth, SkillPoint, SmoothPolicy, TimedResult},
+    soft_reset::{soft_reset, SoftReset, SoftResetPolicy},
+    validate::{validate_game_result, validate_team},
 };
 
-pub struct Env {
+pub struct Env<F = f64> {
     default_mu: f64,      // 25.0
     z: f64,               // 3.0
     default_sigma: f64,   // Mu/Z
     beta: f64,            // Sigma/2.0
     ordinal: OrdinalFunc, // Mu - Z * Sigma
     model: Box<dyn Model>,
+    advantage: Vec<f64>,      // per team slot, empty = none
+    aggregate: AggregateFunc, // sum of members
+    provisional: ProvisionalPolicy,
+    float: PhantomData<F>, // storage type of the ratings passed in and out
+}
+
+impl Default for Env {
+    fn default() -> Self {
+        EnvBuilder::default().build()
+    }
 }
 
 impl Env {
@@ -32,21 +63,71 @@ impl Env {
             default_sigma,
             beta,
             ordinal,
+            advantage: vec![],
+            aggregate: sum_aggregate,
+            provisional: ProvisionalPolicy::default(),
+            float: PhantomData,
         }
     }
 
-    pub fn default() -> Self {
-        EnvBuilder::default().build()
+    /// Switches the rating storage type, e.g. `Env::default().with_float::<f32>()`.
+    pub fn with_float<G: Float>(self) -> Env<G> {
+        Env {
+            default_mu: self.default_mu,
+            z: self.z,
+            default_sigma: self.default_sigma,
+            beta: self.beta,
+            ordinal: self.ordinal,
+            model: self.model,
+            advantage: self.advantage,
+            aggregate: self.aggregate,
+            provisional: self.provisional,
+            float: PhantomData,
+        }
+    }
+}
+
+impl<F: Float> Env<F> {
+    /// Only the aggregate used for predictions; the model keeps its own, so this is set
+    /// together with it by `EnvBuilder::aggregate`.
+    pub(crate) fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Per team slot advantage used when a game sets none; slots past its end get none.
+    pub fn with_advantage(mut self, advantage: Vec<f64>) -> Self {
+        self.advantage = advantage;
+        self
     }
 
-    pub fn new_rating(&self) -> Rating {
-        Rating::new(self.default_mu, self.default_sigma)
+    pub fn with_provisional(mut self, provisional: ProvisionalPolicy) -> Self {
+        self.provisional = provisional;
+        self
     }
 
-    pub fn rate(&self, result: &GameResult) -> Result<Vec<Vec<Rating>>> {
-        let teams = result.teams.clone();
-        let ranks = result.ranks.clone();
+    pub fn new_rating(&self) -> Rating<F> {
+        Rating::new(cast(self.default_mu), cast(self.default_sigma))
+    }
+
+    pub fn rate(&self, result: &GameResult<F>) -> Result<Vec<Vec<Rating<F>>>> {
+        if result.teams.is_empty() {
+            return Err(OpenSkillError::InvalidTeamCount("0"));
+        }
+
+        if result.teams.len() < 2 {
+            return Ok(result.teams.clone());
+        }
+
+        let result = result.cast();
+        validate_game_result(&result)?;
+
+        Ok(cast_teams(
+            &self.model.rate(&self.with_env_advantage(&result)),
+        ))
+    }
 
+    pub fn rate_with_tau(&self, result: &GameResult<F>, tau: f64) -> Result<Vec<Vec<Rating<F>>>> {
         if result.teams.is_empty() {
             return Err(OpenSkillError::InvalidTeamCount("0"));
         }
@@ -55,19 +136,54 @@ impl Env {
             return Ok(result.teams.clone());
         }
 
-        for team in &result.teams {
-            if team.is_empty() {
-                return Err(OpenSkillError::EmptyTeams);
+        let result = result.cast();
+        validate_game_result(&result)?;
+
+        let tau_squad = tau.powi(2);
+        let mut result = self.with_env_advantage(&result).into_owned();
+        for (i, team) in result.teams.iter_mut().enumerate() {
+            for (j, it) in team.iter_mut().enumerate() {
+                if !result.anchored.contains(&(i, j)) {
+                    it.sigma = (it.sigma.powi(2) + tau_squad).sqrt();
+                }
             }
         }
 
-        Ok(self.model.rate(teams, ranks))
+        Ok(cast_teams(&self.model.rate(&result)))
+    }
+
+    /// `rate` for every result, each on its own; in parallel with the `rayon` feature.
+    pub fn rate_batch(&self, results: &[GameResult<F>]) -> Vec<Result<Vec<Vec<Rating<F>>>>>
+    where
+        F: Send + Sync,
+    {
+        par_map(results, |it| self.rate(it))
     }
 
-    pub fn rate_with_tau(&self, result: &GameResult, tau: f64) -> Result<Vec<Vec<Rating>>> {
-        let mut teams = result.teams.clone();
-        let ranks = result.ranks.clone();
+    /// Rates `matches` in order of `time`, starting from `ratings` (unknown players start at
+    /// `new_rating`) and storing every new rating back into it. Matches with no player in
+    /// common are rated together through `rate_batch`; the ratings come out the same as rating
+    /// the matches one by one. Returns the result of every match in the order of `matches`.
+    pub fn rate_scheduled<K: Ord + Clone>(
+        &self,
+        matches: &[TimedResult<K>],
+        ratings: &mut BTreeMap<K, Rating<F>>,
+    ) -> Vec<Result<Vec<Vec<Rating<F>>>>>
+    where
+        F: Send + Sync,
+    {
+        rate_scheduled(matches, ratings, &self.new_rating(), |games| {
+            self.rate_batch(games)
+        })
+    }
 
+    /// Like `rate`, but players that have played fewer than the placement games of the
+    /// provisional policy get the larger placement update. `games` mirrors `result.teams`.
+    pub fn rate_placement(
+        &self,
+        result: &GameResult<F>,
+        games: &[Vec<usize>],
+    ) -> Result<Vec<Vec<Rating<F>>>> {
         if result.teams.is_empty() {
             return Err(OpenSkillError::InvalidTeamCount("0"));
         }
@@ -76,35 +192,203 @@ impl Env {
             return Ok(result.teams.clone());
         }
 
-        for team in &result.teams {
-            if team.is_empty() {
-                return Err(OpenSkillError::EmptyTeams);
-            }
+        let result = result.cast();
+        validate_game_result(&result)?;
+
+        Ok(cast_teams(&rate_placement(
+            self.model.as_ref(),
+            &self.with_env_advantage(&result),
+            games,
+            &self.provisional,
+        )?))
+    }
+
+    pub fn rate_explained(&self, result: &GameResult<F>) -> Result<Vec<TeamExplanation<F>>> {
+        if result.teams.is_empty() {
+            return Err(OpenSkillError::InvalidTeamCount("0"));
         }
 
-        let tau_squad = tau.powi(2);
-        teams = teams
-            .into_iter()
-            .map(|team| {
-                team.iter()
-                    .map(|it| Rating::new(it.mu, (it.sigma.powi(2) + tau_squad).sqrt()))
-                    .collect()
-            })
+        let result = result.cast();
+        let explained = if result.teams.len() < 2 {
+            result
+                .team_ratings(self.aggregate)
+                .iter()
+                .map(|team| TeamExplanation::new(team, vec![], KAPPA))
+                .collect()
+        } else {
+            validate_game_result(&result)?;
+            self.model.rate_explained(&self.with_env_advantage(&result))
+        };
+
+        Ok(explained.iter().map(TeamExplanation::cast).collect())
+    }
+
+    pub fn preview(&self, teams: &[Vec<Rating<F>>]) -> Result<Vec<TeamPreview>> {
+        let result = GameResult::new(cast_teams(teams), vec![1; teams.len()]);
+        let teams = &result.teams;
+        if teams.is_empty() {
+            return Err(OpenSkillError::InvalidTeamCount("0"));
+        }
+        validate_game_result(&result)?;
+
+        let num_teams = teams.len();
+        if num_teams < 2 {
+            let unchanged = changes(&teams[0], &teams[0]);
+            return Ok(vec![TeamPreview {
+                positions: vec![unchanged.clone()],
+                draw: unchanged,
+            }]);
+        }
+
+        let result = self.with_env_advantage(&result);
+        let team_ratings = result.team_ratings(self.aggregate);
+        let mut outcomes: Vec<Vec<usize>> = (0..num_teams)
+            .flat_map(|i| (0..num_teams).map(move |p| (i, p)))
+            .map(|(i, p)| outcome_ranks(&team_ratings, i, p))
             .collect();
+        outcomes.push(vec![1; num_teams]);
 
-        Ok(self.model.rate(teams, ranks))
+        let mut rated = self.model.rate_outcomes(&result, &outcomes);
+        let draw = rated.pop().unwrap_or_default();
+        Ok(teams
+            .iter()
+            .enumerate()
+            .map(|(i, team)| TeamPreview {
+                positions: rated[i * num_teams..(i + 1) * num_teams]
+                    .iter()
+                    .map(|it| changes(team, &it[i]))
+                    .collect(),
+                draw: changes(team, &draw[i]),
+            })
+            .collect())
     }
 
-    pub fn ordinal(&self, rating: &Rating) -> f64 {
-        (self.ordinal)(rating, self.z)
+    fn with_env_advantage<'a>(&self, result: &'a GameResult) -> Cow<'a, GameResult> {
+        if result.advantage.is_empty() && !self.advantage.is_empty() {
+            Cow::Owned(result.clone().advantage(self.advantage.clone()))
+        } else {
+            Cow::Borrowed(result)
+        }
     }
 
-    pub fn predict_draw(&self, team_ratings: &Vec<Vec<Rating>>) -> Result<f64> {
-        predict_draw(team_ratings, self.beta)
+    pub fn learn_advantage(&self, history: &[GameResult<F>], slots: usize) -> Vec<f64> {
+        let history: Vec<GameResult> = history.iter().map(GameResult::cast).collect();
+        learn_advantage(&history, self.beta, slots, self.aggregate)
     }
 
-    pub fn predict_win(&self, team_ratings: &Vec<Vec<Rating>>) -> Result<Vec<f64>> {
-        predict_win(team_ratings, self.beta)
+    pub fn ordinal(&self, rating: &Rating<F>) -> F {
+        cast((self.ordinal)(&rating.cast(), self.z))
+    }
+
+    pub fn status(&self, rating: &Rating<F>, games: usize) -> RatingStatus {
+        self.provisional.status(&rating.cast(), games)
+    }
+
+    pub fn is_provisional(&self, rating: &Rating<F>, games: usize) -> bool {
+        self.status(rating, games) == RatingStatus::Provisional
+    }
+
+    /// Estimated further games until `rating` is established, from the sigma this env's
+    /// model is expected to take off per game. `None` if it never gets there.
+    pub fn games_until_established(
+        &self,
+        rating: &Rating<F>,
+        games: usize,
+    ) -> Result<Option<usize>> {
+        games_until_established(
+            self.model.as_ref(),
+            &rating.cast(),
+            games,
+            &self.provisional,
+        )
+    }
+
+    /// Display scale built around this env's default rating, z and beta.
+    pub fn rating_scale(&self, scale: RatingScaleBuilder) -> Result<RatingScale> {
+        scale.build(self.default_mu, self.default_sigma, self.z, self.beta)
+    }
+
+    /// Converter from Elo and Glicko ratings, with `center` the Elo rating of the default mu.
+    pub fn elo_import(&self, center: f64) -> Result<EloImport> {
+        let scale = self.rating_scale(RatingScaleBuilder::elo().center(center))?;
+        Ok(EloImport::new(scale, self.beta))
+    }
+
+    /// Empty ranking of single items judged pairwise or listwise, see `ItemRanking`.
+    pub fn item_ranking<K: Ord + Clone>(&self) -> ItemRanking<'_, K, F> {
+        ItemRanking::new(self)
+    }
+
+    /// Empty leaderboard ranking by this env's ordinal.
+    pub fn leaderboard<K: Ord + Clone>(&self) -> Leaderboard<K> {
+        Leaderboard::new(self.z, self.ordinal)
+    }
+
+    /// Retrospective ratings over a whole history, see `SmoothPolicy`. Every player starts at
+    /// the default rating; the trajectory holds one point per game of the player, by time.
+    pub fn smooth<K: Ord + Clone>(
+        &self,
+        history: &[TimedResult<K>],
+        policy: SmoothPolicy,
+    ) -> Result<BTreeMap<K, Vec<SkillPoint<F>>>> {
+        let prior = Rating::new(self.default_mu, self.default_sigma);
+        let smoothed = smooth(
+            history,
+            &policy,
+            &prior,
+            self.default_sigma / 100.0,
+            |game| {
+                validate_game_result(game)?;
+                Ok(self.model.rate(&self.with_env_advantage(game)))
+            },
+        )?;
+        Ok(smoothed
+            .into_iter()
+            .map(|(id, points)| (id, points.iter().map(SkillPoint::cast).collect()))
+            .collect())
+    }
+
+    pub fn soft_reset(
+        &self,
+        ratings: &[Rating<F>],
+        policy: SoftResetPolicy,
+    ) -> Result<SoftReset<F>> {
+        let ratings: Vec<Rating> = ratings.iter().map(Rating::cast).collect();
+        soft_reset(&ratings, &policy, self.default_mu, self.z, self.ordinal).map(|it| it.cast())
+    }
+
+    fn prediction_teams(&self, teams: &[Vec<Rating<F>>]) -> Result<Vec<TeamRating>> {
+        let teams = cast_teams(teams);
+        validate_team(&teams)?;
+        let mut team_ratings = to_team_ratings(&teams, None, self.aggregate);
+        apply_advantage(&mut team_ratings, &self.advantage);
+        Ok(team_ratings)
+    }
+
+    pub fn predict_draw(&self, team_ratings: &[Vec<Rating<F>>]) -> Result<f64> {
+        predict_draw_teams(&self.prediction_teams(team_ratings)?, self.beta)
+    }
+
+    pub fn predict_win(&self, team_ratings: &[Vec<Rating<F>>]) -> Result<Vec<f64>> {
+        Ok(predict_win_teams(
+            &self.prediction_teams(team_ratings)?,
+            self.beta,
+        ))
+    }
+
+    pub fn predict_outcome(&self, team_ratings: &[Vec<Rating<F>>]) -> Result<OutcomeProbability> {
+        predict_outcome_teams(&self.prediction_teams(team_ratings)?, self.beta)
+    }
+
+    pub fn win_matrix(&self, team_ratings: &[Vec<Rating<F>>]) -> Result<Vec<Vec<f64>>> {
+        Ok(win_matrix_teams(
+            &self.prediction_teams(team_ratings)?,
+            self.beta,
+        ))
+    }
+
+    pub fn draw_matrix(&self, team_ratings: &[Vec<Rating<F>>]) -> Result<Vec<Vec<f64>>> {
+        draw_matrix_teams(&self.prediction_teams(team_ratings)?, self.beta)
     }
 }
 
@@ -114,10 +398,39 @@ pub(crate) mod test {
 
     use crate::{
         env::Env,
+        env_builder::EnvBuilder,
         error::Result,
-        rating::{GameResult, Rating},
+        model::kind::ModelKind,
+        rating::{cast_teams, GameResult, Rating},
+        soft_reset::SoftResetPolicy,
     };
 
+    pub(crate) fn model_kinds() -> Vec<ModelKind> {
+        vec![
+            ModelKind::PlackettLuce,
+            ModelKind::BradleyTerryPart,
+            ModelKind::BradleyTerryFull,
+            ModelKind::ThurstoneMostellerPart,
+            ModelKind::ThurstoneMostellerFull,
+        ]
+    }
+
+    /// Runs `test` with a default builder for every model.
+    pub(crate) fn for_each_model(test: impl Fn(EnvBuilder)) {
+        for kind in model_kinds() {
+            test(EnvBuilder::default().model(kind));
+        }
+    }
+
+    /// Three uneven teams with mixed ratings.
+    pub(crate) fn teams() -> Vec<Vec<Rating>> {
+        vec![
+            vec![Rating::default(), Rating::new(32.444, 5.123)],
+            vec![Rating::new(73.381, 1.421), Rating::new(25.188, 6.211)],
+            vec![Rating::new(28.0, 3.5)],
+        ]
+    }
+
     pub(crate) fn env_model_test_func(input: GameResult, want: Result<Vec<Vec<Rating>>>, env: Env) {
         let got = env.rate(&input);
         match (got.clone(), want.clone()) {
@@ -137,7 +450,54 @@ pub(crate) mod test {
             (Err(got), Err(want)) => {
                 assert_eq!(got, want, "{got}, {want}");
             }
-            _ => assert!(false, "{got:?}, {want:?}"),
+            _ => panic!("{got:?}, {want:?}"),
         }
     }
+
+    #[test]
+    fn f32_ratings_stay_close_to_f64() {
+        for_each_model(|builder| {
+            let env64 = builder.clone().build();
+            let env32 = builder.build_float::<f32>();
+            let want = env64
+                .rate(&GameResult::new(teams(), vec![2, 1, 3]))
+                .unwrap();
+            let got = env32
+                .rate(&GameResult::new(cast_teams(&teams()), vec![2, 1, 3]))
+                .unwrap();
+            for (g, w) in got.iter().flatten().zip(want.iter().flatten()) {
+                assert!(relative_eq!(g.mu, w.mu as f32, max_relative = 1e-5));
+                assert!(relative_eq!(g.sigma, w.sigma as f32, max_relative = 1e-5));
+            }
+        });
+    }
+
+    #[test]
+    fn f32_predictions_and_helpers() {
+        let env64 = EnvBuilder::default().build();
+        let env32 = EnvBuilder::default().build_float::<f32>();
+        let teams32: Vec<Vec<Rating<f32>>> = cast_teams(&teams());
+
+        let want = env64.predict_win(&teams()).unwrap();
+        let got = env32.predict_win(&teams32).unwrap();
+        for (g, w) in got.iter().zip(&want) {
+            assert!(relative_eq!(g, w, epsilon = 1e-6));
+        }
+        let want = env64.predict_draw(&teams()).unwrap();
+        let got = env32.predict_draw(&teams32).unwrap();
+        assert!(relative_eq!(got, want, epsilon = 1e-6));
+
+        let rating = env32.new_rating();
+        assert_eq!(rating.mu, 25.0f32);
+        assert!(relative_eq!(env32.ordinal(&rating), 0.0f32, epsilon = 1e-5));
+
+        let reset = env32
+            .soft_reset(&teams32[1], SoftResetPolicy::default().fraction(0.5))
+            .unwrap();
+        assert!(relative_eq!(
+            reset.ratings[0].mu,
+            49.1905f32,
+            epsilon = 1e-4
+        ));
+    }
 }
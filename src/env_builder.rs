@@ -7,22 +7,47 @@ use crate::{
         thurstone_mosteller_full::ThurstoneMostellerFull,
         thurstone_mosteller_part::ThurstoneMostellerPart,
     },
-    rating::{default_gamma, default_ordinal, GammaFunc, OrdinalFunc},
+    provisional::ProvisionalPolicy,
+    rating::{
+        default_gamma, default_ordinal, sum_aggregate, AggregateFunc, GammaFunc, OrdinalFunc,
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use num_traits::Float;
+
+#[cfg(feature = "baselines")]
+use crate::model::{
+    elo::Elo,
+    glicko2::Glicko2,
+    trueskill::{TrueSkill, DRAW_PROBABILITY},
 };
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct EnvBuilder {
+    advantage: Option<Vec<f64>>,
+    aggregate: Option<AggregateFunc>,
     beta: Option<f64>,
     gamma: Option<GammaFunc>,
     kappa: Option<f64>,
     model: Option<ModelKind>,
     mu: Option<f64>,
     ordinal: Option<OrdinalFunc>,
+    provisional: Option<ProvisionalPolicy>,
     sigma: Option<f64>,
     z: Option<f64>,
 }
 
 impl EnvBuilder {
+    pub fn advantage(mut self, advantage: Vec<f64>) -> Self {
+        self.advantage = Some(advantage);
+        self
+    }
+
+    pub fn aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
     pub fn beta(mut self, beta: f64) -> Self {
         self.beta = Some(beta);
         self
@@ -53,6 +78,11 @@ impl EnvBuilder {
         self
     }
 
+    pub fn provisional(mut self, provisional: ProvisionalPolicy) -> Self {
+        self.provisional = Some(provisional);
+        self
+    }
+
     pub fn sigma(mut self, sigma: f64) -> Self {
         self.sigma = Some(sigma);
         self
@@ -71,18 +101,40 @@ impl EnvBuilder {
         let kappa = self.kappa.unwrap_or(KAPPA);
         let gamma = self.gamma.unwrap_or(default_gamma);
         let ordinal = self.ordinal.unwrap_or(default_ordinal);
+        let aggregate = self.aggregate.unwrap_or(sum_aggregate);
         let model: Box<dyn Model> = match self.model.unwrap_or_default() {
-            ModelKind::PlackettLuce => Box::new(PlackettLuce::new(beta, kappa, gamma)),
-            ModelKind::BradleyTerryFull => Box::new(BradleyTerryFull::new(beta, kappa, gamma)),
-            ModelKind::BradleyTerryPart => Box::new(BradleyTerryPart::new(beta, kappa, gamma)),
+            ModelKind::PlackettLuce => {
+                Box::new(PlackettLuce::new(beta, kappa, gamma).with_aggregate(aggregate))
+            }
+            ModelKind::BradleyTerryFull => {
+                Box::new(BradleyTerryFull::new(beta, kappa, gamma).with_aggregate(aggregate))
+            }
+            ModelKind::BradleyTerryPart => {
+                Box::new(BradleyTerryPart::new(beta, kappa, gamma).with_aggregate(aggregate))
+            }
             ModelKind::ThurstoneMostellerPart => {
-                Box::new(ThurstoneMostellerPart::new(beta, kappa, gamma))
+                Box::new(ThurstoneMostellerPart::new(beta, kappa, gamma).with_aggregate(aggregate))
             }
             ModelKind::ThurstoneMostellerFull => {
-                Box::new(ThurstoneMostellerFull::new(beta, kappa, gamma))
+                Box::new(ThurstoneMostellerFull::new(beta, kappa, gamma).with_aggregate(aggregate))
             }
+            #[cfg(feature = "baselines")]
+            ModelKind::Elo => Box::new(Elo::default().with_aggregate(aggregate)),
+            #[cfg(feature = "baselines")]
+            ModelKind::Glicko2 => Box::new(Glicko2::default().with_aggregate(aggregate)),
+            #[cfg(feature = "baselines")]
+            ModelKind::TrueSkill => Box::new(
+                TrueSkill::new(beta, sigma / 100.0, DRAW_PROBABILITY).with_aggregate(aggregate),
+            ),
         };
 
         Env::new(model, z, mu, sigma, beta, ordinal)
+            .with_advantage(self.advantage.unwrap_or_default())
+            .with_aggregate(aggregate)
+            .with_provisional(self.provisional.unwrap_or_default())
+    }
+
+    pub fn build_float<F: Float>(self) -> Env<F> {
+        self.build().with_float()
     }
 }
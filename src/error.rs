@@ -6,6 +6,8 @@ pub enum OpenSkillError {
     EmptyTeams,
     #[error("Invalid team count {0}")]
     InvalidTeamCount(&'static str),
+    #[error("Invalid argument {0}")]
+    InvalidArgument(&'static str),
 }
 
-pub type Result<T> = std::result::Result<T, OpenSkillError>;
+pub type Result<T> = core::result::Result<T, OpenSkillError>;
@@ -0,0 +1,134 @@
+use crate::{
+    rating::{Rating, TeamRating},
+    utils::{member_shares, update_team_rating},
+};
+use alloc::vec::Vec;
+use num_traits::Float;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpponentContribution {
+    /// Index of the opposing team; Plackett-Luce also reports the team's own ranking term.
+    pub opponent: usize,
+    pub omega: f64,
+    pub delta: f64,
+    /// Gamma factor applied to `delta` for this opponent.
+    pub gamma: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamExplanation<F = f64> {
+    pub contributions: Vec<OpponentContribution>,
+    pub omega: f64,
+    pub delta: f64,
+    /// Fraction of `omega`/`delta` each member receives; 0.0 for anchored players.
+    pub shares: Vec<f64>,
+    pub ratings: Vec<Rating<F>>,
+}
+
+impl TeamExplanation {
+    pub(crate) fn new(
+        team: &TeamRating,
+        contributions: Vec<OpponentContribution>,
+        epsilon: f64,
+    ) -> Self {
+        let omega = contributions.iter().map(|it| it.omega).sum::<f64>();
+        let delta = contributions.iter().map(|it| it.delta).sum::<f64>();
+        Self {
+            shares: member_shares(team),
+            ratings: update_team_rating(team, omega, delta, epsilon),
+            contributions,
+            omega,
+            delta,
+        }
+    }
+}
+
+impl<F: Float> TeamExplanation<F> {
+    pub fn cast<G: Float>(&self) -> TeamExplanation<G> {
+        TeamExplanation {
+            contributions: self.contributions.clone(),
+            omega: self.omega,
+            delta: self.delta,
+            shares: self.shares.clone(),
+            ratings: self.ratings.iter().map(Rating::cast).collect(),
+        }
+    }
+
+    /// Change in `mu` of `player` caused by `opponent`.
+    pub fn mu_gain(&self, player: usize, opponent: usize) -> f64 {
+        self.contributions
+            .iter()
+            .filter(|it| it.opponent == opponent)
+            .map(|it| it.omega * self.shares[player])
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        env::test::model_kinds,
+        env_builder::EnvBuilder,
+        rating::{GameResult, Rating},
+    };
+    use approx::relative_eq;
+
+    fn game() -> GameResult {
+        GameResult::new(
+            vec![
+                vec![Rating::default(), Rating::new(30.0, 4.0)],
+                vec![Rating::new(20.0, 6.0)],
+                vec![Rating::new(27.0, 3.0), Rating::default()],
+            ],
+            vec![2, 1, 3],
+        )
+        .anchor(2, 1)
+    }
+
+    #[test]
+    fn explained_ratings_match_rate() {
+        for kind in model_kinds() {
+            let env = EnvBuilder::default().model(kind).build();
+            let rated = env.rate(&game()).unwrap();
+            let explained = env.rate_explained(&game()).unwrap();
+            for (team, explanation) in rated.iter().zip(&explained) {
+                for (r, e) in team.iter().zip(&explanation.ratings) {
+                    assert_eq!(r.mu, e.mu);
+                    assert_eq!(r.sigma, e.sigma);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn contributions_add_up_to_mu_change() {
+        let before = game();
+        for kind in model_kinds() {
+            let env = EnvBuilder::default().model(kind).build();
+            let explained = env.rate_explained(&before).unwrap();
+            for (team, explanation) in before.teams.iter().zip(&explained) {
+                let shares = explanation.shares.iter().sum::<f64>();
+                assert!(relative_eq!(shares, 1.0, epsilon = 1e-12));
+                for (player, (b, a)) in team.iter().zip(&explanation.ratings).enumerate() {
+                    let gained = (0..before.teams.len())
+                        .map(|opponent| explanation.mu_gain(player, opponent))
+                        .sum::<f64>();
+                    assert!(relative_eq!(a.mu - b.mu, gained, epsilon = 1e-9));
+                }
+            }
+            assert_eq!(explained[2].shares[1], 0.0);
+        }
+    }
+
+    #[test]
+    fn beating_a_team_is_a_gain() {
+        let env = EnvBuilder::default().build();
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+        let explained = env
+            .rate_explained(&GameResult::new(teams, vec![1, 2]))
+            .unwrap();
+        assert!(explained[0].mu_gain(0, 0) > 0.0);
+        assert!(explained[1].mu_gain(0, 0) < 0.0);
+        assert!(explained[0].contributions.iter().all(|it| it.gamma > 0.0));
+    }
+}
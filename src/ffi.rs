@@ -0,0 +1,366 @@
+//! C ABI over [`Env`], declared in `include/openskill.h`. Teams are passed as flat
+//! `mu`/`sigma` arrays plus `team_count + 1` offsets: team `t` owns players
+//! `offsets[t]..offsets[t + 1]`. Every fallible function returns `OPENSKILL_OK` or one of
+//! the `OPENSKILL_ERROR_*` codes; a panic never unwinds into C but is reported as
+//! `OPENSKILL_ERROR_PANIC`.
+
+use crate::{
+    constant::{DEFAULT_BETA, DEFAULT_MU, DEFAULT_SIGMA, DEFAULT_Z, KAPPA},
+    env::Env,
+    env_builder::EnvBuilder,
+    error::{OpenSkillError, Result},
+    model::kind::ModelKind,
+    rating::{GameResult, Rating},
+};
+use core::{
+    ffi::{c_char, c_int, CStr},
+    iter::zip,
+    slice,
+};
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    process,
+};
+
+pub const OPENSKILL_OK: c_int = 0;
+pub const OPENSKILL_ERROR_EMPTY_TEAMS: c_int = 1;
+pub const OPENSKILL_ERROR_INVALID_TEAM_COUNT: c_int = 2;
+pub const OPENSKILL_ERROR_INVALID_ARGUMENT: c_int = 3;
+pub const OPENSKILL_ERROR_PANIC: c_int = 4;
+
+pub const OPENSKILL_MODEL_PLACKETT_LUCE: c_int = 0;
+pub const OPENSKILL_MODEL_BRADLEY_TERRY_PART: c_int = 1;
+pub const OPENSKILL_MODEL_BRADLEY_TERRY_FULL: c_int = 2;
+pub const OPENSKILL_MODEL_THURSTONE_MOSTELLER_PART: c_int = 3;
+pub const OPENSKILL_MODEL_THURSTONE_MOSTELLER_FULL: c_int = 4;
+
+/// Opaque handle created by `openskill_env_new` and released by `openskill_env_free`.
+pub struct OpenSkillEnv(Env);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct OpenSkillConfig {
+    /// One of the `OPENSKILL_MODEL_*` constants.
+    pub model: c_int,
+    pub mu: f64,
+    pub sigma: f64,
+    pub beta: f64,
+    pub kappa: f64,
+    pub z: f64,
+}
+
+impl Default for OpenSkillConfig {
+    fn default() -> Self {
+        Self {
+            model: OPENSKILL_MODEL_PLACKETT_LUCE,
+            mu: DEFAULT_MU,
+            sigma: DEFAULT_SIGMA,
+            beta: DEFAULT_BETA,
+            kappa: KAPPA,
+            z: DEFAULT_Z,
+        }
+    }
+}
+
+impl OpenSkillConfig {
+    fn build(&self) -> Result<Env> {
+        let model = match self.model {
+            OPENSKILL_MODEL_PLACKETT_LUCE => ModelKind::PlackettLuce,
+            OPENSKILL_MODEL_BRADLEY_TERRY_PART => ModelKind::BradleyTerryPart,
+            OPENSKILL_MODEL_BRADLEY_TERRY_FULL => ModelKind::BradleyTerryFull,
+            OPENSKILL_MODEL_THURSTONE_MOSTELLER_PART => ModelKind::ThurstoneMostellerPart,
+            OPENSKILL_MODEL_THURSTONE_MOSTELLER_FULL => ModelKind::ThurstoneMostellerFull,
+            _ => return Err(OpenSkillError::InvalidArgument("unknown model")),
+        };
+        Ok(EnvBuilder::default()
+            .model(model)
+            .mu(self.mu)
+            .sigma(self.sigma)
+            .beta(self.beta)
+            .kappa(self.kappa)
+            .z(self.z)
+            .build())
+    }
+}
+
+fn error_code(err: OpenSkillError) -> c_int {
+    match err {
+        OpenSkillError::EmptyTeams => OPENSKILL_ERROR_EMPTY_TEAMS,
+        OpenSkillError::InvalidTeamCount(_) => OPENSKILL_ERROR_INVALID_TEAM_COUNT,
+        OpenSkillError::InvalidArgument(_) => OPENSKILL_ERROR_INVALID_ARGUMENT,
+    }
+}
+
+/// Runs the body of an exported function, turning errors and panics into codes.
+fn status(body: impl FnOnce() -> Result<()>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(result) => result.map_or_else(error_code, |_| OPENSKILL_OK),
+        Err(_) => OPENSKILL_ERROR_PANIC,
+    }
+}
+
+/// For exported functions without an error code: a panic aborts instead of unwinding.
+fn no_unwind<T>(body: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| process::abort())
+}
+
+unsafe fn env_ref<'a>(env: *const OpenSkillEnv) -> Result<&'a Env> {
+    env.as_ref()
+        .map(|it| &it.0)
+        .ok_or(OpenSkillError::InvalidArgument("env is null"))
+}
+
+unsafe fn input<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T]> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(OpenSkillError::InvalidArgument("input array is null")),
+        (false, _) => Ok(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+unsafe fn output<'a, T>(ptr: *mut T, len: usize) -> Result<&'a mut [T]> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&mut []),
+        (true, _) => Err(OpenSkillError::InvalidArgument("output array is null")),
+        (false, _) => Ok(slice::from_raw_parts_mut(ptr, len)),
+    }
+}
+
+/// Reads the player count from `offsets` and splits the flat arrays into teams.
+unsafe fn read_teams(
+    mu: *const f64,
+    sigma: *const f64,
+    offsets: *const usize,
+    team_count: usize,
+) -> Result<Vec<Vec<Rating>>> {
+    let len = team_count
+        .checked_add(1)
+        .ok_or(OpenSkillError::InvalidArgument("team_count is too large"))?;
+    let offsets = input(offsets, len)?;
+    if offsets[0] != 0 || offsets.windows(2).any(|it| it[0] > it[1]) {
+        return Err(OpenSkillError::InvalidArgument(
+            "team offsets must start at 0 and never decrease",
+        ));
+    }
+    let players = offsets[team_count];
+    let mu = input(mu, players)?;
+    let sigma = input(sigma, players)?;
+    Ok(offsets
+        .windows(2)
+        .map(|it| {
+            (it[0]..it[1])
+                .map(|p| Rating::new(mu[p], sigma[p]))
+                .collect()
+        })
+        .collect())
+}
+
+/// Default configuration, to be adjusted before `openskill_env_new`.
+#[no_mangle]
+pub extern "C" fn openskill_config_default() -> OpenSkillConfig {
+    no_unwind(OpenSkillConfig::default)
+}
+
+/// Returns a static, NUL terminated description of an `OPENSKILL_*` code.
+#[no_mangle]
+pub extern "C" fn openskill_error_message(code: c_int) -> *const c_char {
+    no_unwind(|| {
+        let message: &CStr = match code {
+            OPENSKILL_OK => c"ok",
+            OPENSKILL_ERROR_EMPTY_TEAMS => c"empty teams",
+            OPENSKILL_ERROR_INVALID_TEAM_COUNT => c"invalid team count",
+            OPENSKILL_ERROR_INVALID_ARGUMENT => c"invalid argument",
+            OPENSKILL_ERROR_PANIC => c"internal panic",
+            _ => c"unknown error",
+        };
+        message.as_ptr()
+    })
+}
+
+/// Creates an env from `config`, or from `openskill_config_default()` when it is null.
+///
+/// # Safety
+/// `config` must be null or valid, `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskill_env_new(
+    config: *const OpenSkillConfig,
+    out: *mut *mut OpenSkillEnv,
+) -> c_int {
+    status(|| {
+        let out = out
+            .as_mut()
+            .ok_or(OpenSkillError::InvalidArgument("out is null"))?;
+        let env = config.as_ref().copied().unwrap_or_default().build()?;
+        *out = Box::into_raw(Box::new(OpenSkillEnv(env)));
+        Ok(())
+    })
+}
+
+/// Releases an env; null is ignored.
+///
+/// # Safety
+/// `env` must be null or come from `openskill_env_new`, and is not usable afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn openskill_env_free(env: *mut OpenSkillEnv) {
+    no_unwind(|| {
+        if !env.is_null() {
+            drop(Box::from_raw(env));
+        }
+    })
+}
+
+/// Rates one game. `ranks` holds `team_count` ranks (lower is better) or is null for
+/// finishing order; `out_mu`/`out_sigma` receive one entry per player.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the lengths described above.
+#[no_mangle]
+pub unsafe extern "C" fn openskill_rate(
+    env: *const OpenSkillEnv,
+    mu: *const f64,
+    sigma: *const f64,
+    offsets: *const usize,
+    team_count: usize,
+    ranks: *const usize,
+    out_mu: *mut f64,
+    out_sigma: *mut f64,
+) -> c_int {
+    status(|| {
+        let env = env_ref(env)?;
+        let teams = read_teams(mu, sigma, offsets, team_count)?;
+        let ranks = if ranks.is_null() {
+            (1..=team_count).collect()
+        } else {
+            input(ranks, team_count)?.to_vec()
+        };
+        let players = teams.iter().map(Vec::len).sum();
+        let out_mu = output(out_mu, players)?;
+        let out_sigma = output(out_sigma, players)?;
+
+        let rated = env.rate(&GameResult::new(teams, ranks))?;
+        for (rating, (mu, sigma)) in zip(rated.iter().flatten(), zip(out_mu, out_sigma)) {
+            *mu = rating.mu;
+            *sigma = rating.sigma;
+        }
+        Ok(())
+    })
+}
+
+/// Writes the win probability of each of the `team_count` teams to `out`.
+///
+/// # Safety
+/// Every pointer must be valid for the lengths implied by `offsets` and `team_count`.
+#[no_mangle]
+pub unsafe extern "C" fn openskill_predict_win(
+    env: *const OpenSkillEnv,
+    mu: *const f64,
+    sigma: *const f64,
+    offsets: *const usize,
+    team_count: usize,
+    out: *mut f64,
+) -> c_int {
+    status(|| {
+        let env = env_ref(env)?;
+        let teams = read_teams(mu, sigma, offsets, team_count)?;
+        let out = output(out, team_count)?;
+        out.copy_from_slice(&env.predict_win(&teams)?);
+        Ok(())
+    })
+}
+
+/// Writes the probability that all `team_count` teams draw to `out`.
+///
+/// # Safety
+/// Every pointer must be valid for the lengths implied by `offsets` and `team_count`.
+#[no_mangle]
+pub unsafe extern "C" fn openskill_predict_draw(
+    env: *const OpenSkillEnv,
+    mu: *const f64,
+    sigma: *const f64,
+    offsets: *const usize,
+    team_count: usize,
+    out: *mut f64,
+) -> c_int {
+    status(|| {
+        let env = env_ref(env)?;
+        let teams = read_teams(mu, sigma, offsets, team_count)?;
+        let out = out
+            .as_mut()
+            .ok_or(OpenSkillError::InvalidArgument("out is null"))?;
+        *out = env.predict_draw(&teams)?;
+        Ok(())
+    })
+}
+
+/// Writes the ordinal of each of the `count` ratings to `out`.
+///
+/// # Safety
+/// `mu`, `sigma` and `out` must be valid for `count` elements.
+#[no_mangle]
+pub unsafe extern "C" fn openskill_ordinal(
+    env: *const OpenSkillEnv,
+    mu: *const f64,
+    sigma: *const f64,
+    count: usize,
+    out: *mut f64,
+) -> c_int {
+    status(|| {
+        let env = env_ref(env)?;
+        let ratings = zip(input(mu, count)?, input(sigma, count)?);
+        for ((mu, sigma), out) in ratings.zip(output(out, count)?) {
+            *out = env.ordinal(&Rating::new(*mu, *sigma));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checked_in_header_is_up_to_date() {
+        assert_eq!(
+            include_str!("../include/openskill.h"),
+            include_str!(concat!(env!("OUT_DIR"), "/openskill.h"))
+        );
+    }
+
+    #[test]
+    fn errors_map_to_codes() {
+        assert_eq!(
+            error_code(OpenSkillError::EmptyTeams),
+            OPENSKILL_ERROR_EMPTY_TEAMS
+        );
+        let config = OpenSkillConfig {
+            model: 42,
+            ..Default::default()
+        };
+        assert!(config.build().is_err());
+        assert!(!openskill_error_message(-1).is_null());
+    }
+
+    #[test]
+    fn panics_become_codes() {
+        let code = status(|| panic!("boom"));
+        assert_eq!(code, OPENSKILL_ERROR_PANIC);
+        assert_eq!(status(|| Ok(())), OPENSKILL_OK);
+    }
+
+    #[test]
+    fn huge_team_count_is_rejected() {
+        let offsets = [0usize];
+        let teams = unsafe {
+            read_teams(
+                core::ptr::null(),
+                core::ptr::null(),
+                offsets.as_ptr(),
+                usize::MAX,
+            )
+        };
+        assert_eq!(
+            teams.unwrap_err(),
+            OpenSkillError::InvalidArgument("team_count is too large")
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    error::{OpenSkillError, Result},
+    predict_win::predict_win,
+    rating::Rating,
+    scale::RatingScale,
+};
+use alloc::vec;
+use core::f64::consts::{LN_10, PI};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Glicko points per unit of the Glicko-2 internal scale.
+pub const GLICKO2_SCALE: f64 = 173.7178;
+
+/// A historical game between two rated players of the source system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImportGame {
+    pub rating: f64,
+    /// Rating deviation, 0.0 for plain Elo.
+    pub deviation: f64,
+    pub opponent: f64,
+    pub opponent_deviation: f64,
+    /// 1.0 for a win, 0.5 for a draw and 0.0 for a loss.
+    pub score: f64,
+}
+
+/// How well converted ratings reproduce the source system's predictions on a sample of games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionReport {
+    pub games: usize,
+    /// Mean and largest difference between the source's expected score and `predict_win`.
+    pub mean_abs_error: f64,
+    pub max_abs_error: f64,
+    /// Brier score of the source's expected score and of `predict_win` against the actual scores.
+    pub source_brier: f64,
+    pub converted_brier: f64,
+}
+
+/// Seeds ratings from Elo, Glicko and Glicko-2 (see `Env::elo_import`). Ratings map linearly
+/// through an Elo `RatingScale`, so 400 points are 10:1 odds here as well, and deviations are
+/// scaled the same way.
+#[derive(Debug, Clone)]
+pub struct EloImport {
+    scale: RatingScale,
+    beta: f64,
+}
+
+impl EloImport {
+    pub(crate) fn new(scale: RatingScale, beta: f64) -> Self {
+        Self { scale, beta }
+    }
+
+    /// An Elo (or Glicko) rating with its deviation; plain Elo has none, but a rating still
+    /// needs some uncertainty to keep moving, e.g. 50 to 100 points for an active player.
+    pub fn elo(&self, rating: f64, deviation: f64) -> Rating {
+        self.scale.rating(rating, deviation)
+    }
+
+    pub fn glicko(&self, rating: f64, rd: f64) -> Rating {
+        self.elo(rating, rd)
+    }
+
+    /// A rating on the Glicko-2 internal scale; the volatility has no counterpart and is dropped.
+    pub fn glicko2(&self, mu: f64, phi: f64) -> Rating {
+        self.glicko(1500.0 + GLICKO2_SCALE * mu, GLICKO2_SCALE * phi)
+    }
+
+    /// Compares the source's expected score of every game, Glicko's `E` (plain Elo when the
+    /// opponent deviation is 0), with `predict_win` on the converted ratings.
+    pub fn report(&self, games: &[ImportGame]) -> Result<ConversionReport> {
+        if games.is_empty() {
+            return Err(OpenSkillError::InvalidArgument(
+                "games must contain atleast 1 game",
+            ));
+        }
+
+        let mut abs_error = 0.0;
+        let mut max_abs_error: f64 = 0.0;
+        let mut source_brier = 0.0;
+        let mut converted_brier = 0.0;
+        for game in games {
+            let source = glicko_expected(game.rating, game.opponent, game.opponent_deviation);
+            let teams = vec![
+                vec![self.glicko(game.rating, game.deviation)],
+                vec![self.glicko(game.opponent, game.opponent_deviation)],
+            ];
+            let converted = predict_win(&teams, self.beta)?[0];
+
+            abs_error += (source - converted).abs();
+            max_abs_error = max_abs_error.max((source - converted).abs());
+            source_brier += (source - game.score).powi(2);
+            converted_brier += (converted - game.score).powi(2);
+        }
+
+        let n = games.len() as f64;
+        Ok(ConversionReport {
+            games: games.len(),
+            mean_abs_error: abs_error / n,
+            max_abs_error,
+            source_brier: source_brier / n,
+            converted_brier: converted_brier / n,
+        })
+    }
+}
+
+fn glicko_expected(rating: f64, opponent: f64, opponent_rd: f64) -> f64 {
+    let q = LN_10 / 400.0;
+    let g = 1.0 / (1.0 + 3.0 * (q * opponent_rd).powi(2) / PI.powi(2)).sqrt();
+    1.0 / (1.0 + 10f64.powf(-g * (rating - opponent) / 400.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::env_builder::EnvBuilder;
+    use alloc::vec::Vec;
+    use approx::relative_eq;
+
+    #[test]
+    fn elo_odds_are_preserved_at_400_points() {
+        let env = EnvBuilder::default().build();
+        let import = env.elo_import(1500.0).unwrap();
+        let a = import.elo(1900.0, 0.0);
+        let b = import.elo(1500.0, 0.0);
+        assert!(relative_eq!(b.mu, 25.0));
+        let p = env.predict_win(&[vec![a], vec![b]]).unwrap()[0];
+        assert!(relative_eq!(
+            p,
+            glicko_expected(1900.0, 1500.0, 0.0),
+            epsilon = 1e-9
+        ));
+    }
+
+    #[test]
+    fn glicko2_matches_glicko() {
+        let env = EnvBuilder::default().build();
+        let import = env.elo_import(1500.0).unwrap();
+        let a = import.glicko2(0.5, 1.2);
+        let b = import.glicko(1500.0 + 0.5 * GLICKO2_SCALE, 1.2 * GLICKO2_SCALE);
+        assert!(relative_eq!(a.mu, b.mu));
+        assert!(relative_eq!(a.sigma, b.sigma));
+        assert!(import.glicko(1800.0, 350.0).sigma > import.glicko(1800.0, 50.0).sigma);
+    }
+
+    #[test]
+    fn report_on_elo_sample() {
+        let env = EnvBuilder::default().build();
+        let import = env.elo_import(1500.0).unwrap();
+        let games: Vec<ImportGame> = (0..41)
+            .map(|i| {
+                let gap = (i as f64 - 20.0) * 25.0;
+                ImportGame {
+                    rating: 1500.0 + gap,
+                    deviation: 0.0,
+                    opponent: 1500.0,
+                    opponent_deviation: 0.0,
+                    score: if gap >= 0.0 { 1.0 } else { 0.0 },
+                }
+            })
+            .collect();
+        let report = import.report(&games).unwrap();
+        assert_eq!(report.games, 41);
+        // probit and logistic curves agree at 0 and 400 points and stay close in between
+        assert!(report.mean_abs_error < 0.01, "{report:?}");
+        assert!(report.max_abs_error < 0.02, "{report:?}");
+        assert!((report.source_brier - report.converted_brier).abs() < 0.01);
+    }
+
+    #[test]
+    fn report_needs_games() {
+        let env = EnvBuilder::default().build();
+        let import = env.elo_import(1500.0).unwrap();
+        assert_eq!(
+            import.report(&[]).unwrap_err(),
+            OpenSkillError::InvalidArgument("games must contain atleast 1 game")
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    env::Env,
+    error::{OpenSkillError, Result},
+    math::normal,
+    rating::{cast, GameResult, Rating},
+    validate::validate_unique,
+};
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use num_traits::Float;
+
+/// One comparison of items by an annotator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Judgment<K> {
+    /// `winner` was preferred over `loser`.
+    Preferred {
+        winner: K,
+        loser: K,
+    },
+    Tie(K, K),
+    /// Items best first, without ties.
+    Ranked(Vec<K>),
+}
+
+impl<K> Judgment<K> {
+    pub fn preferred(winner: K, loser: K) -> Self {
+        Self::Preferred { winner, loser }
+    }
+
+    fn items(&self) -> Vec<&K> {
+        match self {
+            Self::Preferred { winner, loser } => vec![winner, loser],
+            Self::Tie(a, b) => vec![a, b],
+            Self::Ranked(items) => items.iter().collect(),
+        }
+    }
+
+    fn ranks(&self) -> Vec<usize> {
+        match self {
+            Self::Preferred { .. } => vec![1, 2],
+            Self::Tie(..) => vec![1, 1],
+            Self::Ranked(items) => (1..=items.len()).collect(),
+        }
+    }
+}
+
+/// An item with its place in the ranking and a confidence interval for its `mu`.
+#[derive(Debug, Clone)]
+pub struct RankedItem<K, F = f64> {
+    pub id: K,
+    /// 1 based, by ordinal; equal ordinals are ordered by id.
+    pub rank: usize,
+    pub rating: Rating<F>,
+    pub ordinal: F,
+    pub lower: F,
+    pub upper: F,
+}
+
+/// Ranks single items, e.g. models or designs, from pairwise and listwise judgments. Every
+/// item is a team of one and every judgment a game rated by the env, weighted by the
+/// reliability of its annotator.
+pub struct ItemRanking<'a, K, F = f64> {
+    env: &'a Env<F>,
+    items: BTreeMap<K, Rating<F>>,
+    reliability: BTreeMap<String, f64>,
+}
+
+impl<'a, K: Ord + Clone, F: Float> ItemRanking<'a, K, F> {
+    pub fn new(env: &'a Env<F>) -> Self {
+        Self {
+            env,
+            items: BTreeMap::new(),
+            reliability: BTreeMap::new(),
+        }
+    }
+
+    /// Weight of the judgments of `annotator`, 1.0 unless set. 0.0 ignores the annotator.
+    pub fn set_reliability(&mut self, annotator: &str, weight: f64) -> Result<()> {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(OpenSkillError::InvalidArgument(
+                "reliability must be a finite non-negative number",
+            ));
+        }
+        self.reliability.insert(annotator.into(), weight);
+        Ok(())
+    }
+
+    pub fn reliability(&self, annotator: &str) -> f64 {
+        self.reliability.get(annotator).copied().unwrap_or(1.0)
+    }
+
+    /// Adds `item` with `rating`, or replaces its rating.
+    pub fn insert(&mut self, item: K, rating: Rating<F>) {
+        self.items.insert(item, rating);
+    }
+
+    /// `None` for items that were never judged or inserted.
+    pub fn rating(&self, item: &K) -> Option<&Rating<F>> {
+        self.items.get(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Rates `judgment` with full weight. Unknown items start at the default rating.
+    pub fn judge(&mut self, judgment: &Judgment<K>) -> Result<()> {
+        self.judge_weighted(judgment, 1.0)
+    }
+
+    /// Rates `judgment` weighted by the reliability of `annotator`.
+    pub fn judge_by(&mut self, annotator: &str, judgment: &Judgment<K>) -> Result<()> {
+        self.judge_weighted(judgment, self.reliability(annotator))
+    }
+
+    fn judge_weighted(&mut self, judgment: &Judgment<K>, weight: f64) -> Result<()> {
+        let items = judgment.items();
+        if items.len() < 2 {
+            return Err(OpenSkillError::InvalidArgument(
+                "a judgment must compare atleast 2 items",
+            ));
+        }
+        validate_unique(
+            items.iter().copied(),
+            "an item can only appear once per judgment",
+        )?;
+
+        let teams = items
+            .iter()
+            .map(|it| {
+                vec![self
+                    .items
+                    .get(*it)
+                    .cloned()
+                    .unwrap_or_else(|| self.env.new_rating())]
+            })
+            .collect();
+        let rated = self
+            .env
+            .rate(&GameResult::new(teams, judgment.ranks()).weight(weight))?;
+        for (item, rating) in items.into_iter().zip(rated.into_iter().flatten()) {
+            self.items.insert(item.clone(), rating);
+        }
+        Ok(())
+    }
+
+    /// Every item best first, with `mu ± q * sigma` for the two sided normal quantile `q` of
+    /// `confidence`, e.g. 0.95.
+    pub fn ranking(&self, confidence: f64) -> Result<Vec<RankedItem<K, F>>> {
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(OpenSkillError::InvalidArgument(
+                "confidence must be between 0 and 1",
+            ));
+        }
+        let q: F = cast(normal().inverse_cdf((1.0 + confidence) / 2.0));
+
+        let mut ranked: Vec<(F, &K, &Rating<F>)> = self
+            .items
+            .iter()
+            .map(|(id, rating)| (self.env.ordinal(rating), id, rating))
+            .collect();
+        // the map already orders ids, a stable sort keeps them for equal ordinals
+        ranked.sort_by(|a, b| cast::<F, f64>(b.0).total_cmp(&cast(a.0)));
+        Ok(ranked
+            .into_iter()
+            .enumerate()
+            .map(|(i, (ordinal, id, rating))| RankedItem {
+                id: id.clone(),
+                rank: i + 1,
+                rating: rating.clone(),
+                ordinal,
+                lower: rating.mu - q * rating.sigma,
+                upper: rating.mu + q * rating.sigma,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::env_builder::EnvBuilder;
+    use approx::relative_eq;
+
+    fn ids(ranking: &[RankedItem<&'static str>]) -> Vec<&'static str> {
+        ranking.iter().map(|it| it.id).collect()
+    }
+
+    #[test]
+    fn judgments_order_items() {
+        let env = EnvBuilder::default().build();
+        let mut items = env.item_ranking();
+        for _ in 0..10 {
+            items.judge(&Judgment::preferred("b", "c")).unwrap();
+            items.judge(&Judgment::Ranked(vec!["a", "b", "d"])).unwrap();
+            items.judge(&Judgment::preferred("d", "c")).unwrap();
+        }
+        let ranking = items.ranking(0.95).unwrap();
+        assert_eq!(ids(&ranking), vec!["a", "b", "d", "c"]);
+        assert_eq!(ranking[0].rank, 1);
+        assert_eq!(ranking[0].ordinal, env.ordinal(items.rating(&"a").unwrap()));
+    }
+
+    #[test]
+    fn ties_pull_items_together() {
+        let env = EnvBuilder::default().build();
+        let mut items = ItemRanking::new(&env);
+        items.insert("a", Rating::new(30.0, 5.0));
+        items.insert("b", Rating::new(20.0, 5.0));
+        items.judge(&Judgment::Tie("a", "b")).unwrap();
+        assert!(items.rating(&"a").unwrap().mu < 30.0);
+        assert!(items.rating(&"b").unwrap().mu > 20.0);
+    }
+
+    #[test]
+    fn reliability_weights_annotators() {
+        let env = EnvBuilder::default().build();
+        let mut items = ItemRanking::new(&env);
+        items.set_reliability("spammer", 0.0).unwrap();
+        items.set_reliability("novice", 0.5).unwrap();
+        assert_eq!(items.reliability("expert"), 1.0);
+
+        items
+            .judge_by("spammer", &Judgment::preferred("a", "b"))
+            .unwrap();
+        assert_eq!(items.rating(&"a").unwrap().mu, env.new_rating().mu);
+
+        items
+            .judge_by("novice", &Judgment::preferred("c", "d"))
+            .unwrap();
+        items
+            .judge_by("expert", &Judgment::preferred("e", "f"))
+            .unwrap();
+        let gain = |id| items.rating(&id).unwrap().mu - env.new_rating().mu;
+        assert!(gain("c") > 0.0);
+        assert!(gain("c") < gain("e"));
+
+        assert!(items.set_reliability("broken", -1.0).is_err());
+        assert!(items.set_reliability("broken", f64::NAN).is_err());
+    }
+
+    #[test]
+    fn intervals_come_from_sigma() {
+        let env = EnvBuilder::default().build();
+        let mut items = ItemRanking::new(&env);
+        items.judge(&Judgment::preferred("a", "b")).unwrap();
+        let narrow = items.ranking(0.5).unwrap();
+        let wide = items.ranking(0.95).unwrap();
+        for (narrow, wide) in narrow.iter().zip(&wide) {
+            let rating = &wide.rating;
+            assert!(relative_eq!(
+                wide.upper - rating.mu,
+                1.959964 * rating.sigma,
+                epsilon = 1e-4
+            ));
+            assert!(relative_eq!(
+                rating.mu - wide.lower,
+                wide.upper - rating.mu,
+                epsilon = 1e-9
+            ));
+            assert!(narrow.upper < wide.upper && narrow.lower > wide.lower);
+        }
+        assert!(items.ranking(1.0).is_err());
+        assert!(items.ranking(0.0).is_err());
+    }
+
+    #[test]
+    fn nan_ratings_keep_a_total_order() {
+        let env = EnvBuilder::default().build();
+        let mut items = ItemRanking::new(&env);
+        for (id, mu) in [("a", 30.0), ("b", f64::NAN), ("c", 20.0), ("d", 25.0)] {
+            items.insert(id, Rating::new(mu, 1.0));
+        }
+        let ranking = items.ranking(0.9).unwrap();
+        // NaN sorts above every number, the rest stay ordered
+        assert_eq!(ids(&ranking), vec!["b", "a", "d", "c"]);
+    }
+
+    #[test]
+    fn invalid_judgments_change_nothing() {
+        let env = EnvBuilder::default().build();
+        let mut items = ItemRanking::new(&env);
+        assert_eq!(
+            items.judge(&Judgment::Ranked(vec!["a"])).unwrap_err(),
+            OpenSkillError::InvalidArgument("a judgment must compare atleast 2 items")
+        );
+        assert_eq!(
+            items.judge(&Judgment::Tie("a", "a")).unwrap_err(),
+            OpenSkillError::InvalidArgument("an item can only appear once per judgment")
+        );
+        assert!(items.is_empty());
+        assert!(items.ranking(0.9).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,560 @@
+use crate::error::{OpenSkillError, Result};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// A visible tier, entered once the hidden ordinal reaches `min_ordinal`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tier {
+    pub name: String,
+    pub min_ordinal: f64,
+    pub divisions: u32,
+}
+
+impl Tier {
+    pub fn new(name: impl Into<String>, min_ordinal: f64, divisions: u32) -> Self {
+        Self {
+            name: name.into(),
+            min_ordinal,
+            divisions,
+        }
+    }
+}
+
+/// Bronze to Grandmaster over the ordinal range of the default env.
+pub fn default_tiers() -> Vec<Tier> {
+    vec![
+        Tier::new("Bronze", -5.0, 4),
+        Tier::new("Silver", 5.0, 4),
+        Tier::new("Gold", 12.0, 4),
+        Tier::new("Platinum", 19.0, 4),
+        Tier::new("Diamond", 26.0, 4),
+        Tier::new("Master", 33.0, 1),
+        Tier::new("Grandmaster", 40.0, 1),
+    ]
+}
+
+/// The visible rank of one player; this is the state to persist between games.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LadderRank {
+    pub tier: usize,
+    /// 0 is the lowest division of the tier.
+    pub division: u32,
+    pub points: u32,
+    /// Games left in which a loss can not drop the player out of `tier`.
+    pub protection: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Movement {
+    Stayed,
+    Promoted,
+    Demoted,
+    /// Would have been demoted out of the tier but protection kept the player in it.
+    Protected,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LadderUpdate {
+    pub rank: LadderRank,
+    /// Points won or lost before promotions and demotions are applied.
+    pub points: i64,
+    pub movement: Movement,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LadderBuilder {
+    base_points: Option<f64>,
+    gap_factor: Option<f64>,
+    max_points: Option<f64>,
+    points_per_division: Option<u32>,
+    protection_games: Option<u32>,
+    tiers: Option<Vec<Tier>>,
+}
+
+impl LadderBuilder {
+    /// Points won or lost in a game when the visible rank matches the hidden rating.
+    pub fn base_points(mut self, base_points: f64) -> Self {
+        self.base_points = Some(base_points);
+        self
+    }
+
+    /// Extra points won (and fewer lost) per point the hidden rating is above the visible rank.
+    pub fn gap_factor(mut self, gap_factor: f64) -> Self {
+        self.gap_factor = Some(gap_factor);
+        self
+    }
+
+    /// Cap on the points won or lost in a single game.
+    pub fn max_points(mut self, max_points: f64) -> Self {
+        self.max_points = Some(max_points);
+        self
+    }
+
+    pub fn points_per_division(mut self, points_per_division: u32) -> Self {
+        self.points_per_division = Some(points_per_division);
+        self
+    }
+
+    /// Games after a tier promotion in which the player can't be demoted out of the tier.
+    pub fn protection_games(mut self, protection_games: u32) -> Self {
+        self.protection_games = Some(protection_games);
+        self
+    }
+
+    /// Tiers from lowest to highest, by increasing `min_ordinal`.
+    pub fn tiers(mut self, tiers: Vec<Tier>) -> Self {
+        self.tiers = Some(tiers);
+        self
+    }
+
+    pub fn build(self) -> Result<Ladder> {
+        let ladder = Ladder {
+            base_points: self.base_points.unwrap_or(20.0),
+            gap_factor: self.gap_factor.unwrap_or(0.05),
+            max_points: self.max_points.unwrap_or(50.0),
+            points_per_division: self.points_per_division.unwrap_or(100),
+            protection_games: self.protection_games.unwrap_or(3),
+            tiers: self.tiers.unwrap_or_else(default_tiers),
+        };
+        ladder.validate()?;
+        Ok(ladder)
+    }
+}
+
+/// Visible ladder points and tiers layered over the hidden `Env::ordinal`. Points won and lost
+/// depend on the gap between visible rank and hidden rating, so the ladder converges toward it.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "LadderBuilder", into = "LadderBuilder")
+)]
+pub struct Ladder {
+    base_points: f64,
+    gap_factor: f64,
+    max_points: f64,
+    points_per_division: u32,
+    protection_games: u32,
+    tiers: Vec<Tier>,
+}
+
+impl Default for Ladder {
+    fn default() -> Self {
+        LadderBuilder::default().build().unwrap()
+    }
+}
+
+impl TryFrom<LadderBuilder> for Ladder {
+    type Error = OpenSkillError;
+
+    fn try_from(builder: LadderBuilder) -> Result<Self> {
+        builder.build()
+    }
+}
+
+impl From<Ladder> for LadderBuilder {
+    fn from(ladder: Ladder) -> Self {
+        Self {
+            base_points: Some(ladder.base_points),
+            gap_factor: Some(ladder.gap_factor),
+            max_points: Some(ladder.max_points),
+            points_per_division: Some(ladder.points_per_division),
+            protection_games: Some(ladder.protection_games),
+            tiers: Some(ladder.tiers),
+        }
+    }
+}
+
+impl Ladder {
+    fn validate(&self) -> Result<()> {
+        if self.tiers.len() < 2 {
+            return Err(OpenSkillError::InvalidArgument(
+                "ladder must have atleast 2 tiers",
+            ));
+        }
+        if self.tiers.iter().any(|it| it.divisions == 0) {
+            return Err(OpenSkillError::InvalidArgument(
+                "every tier must have atleast 1 division",
+            ));
+        }
+        if !self
+            .tiers
+            .windows(2)
+            .all(|it| it[0].min_ordinal.is_finite() && it[0].min_ordinal < it[1].min_ordinal)
+            || !self.tiers[self.tiers.len() - 1].min_ordinal.is_finite()
+        {
+            return Err(OpenSkillError::InvalidArgument(
+                "tier ordinals must be finite and increasing",
+            ));
+        }
+        if self.points_per_division == 0 {
+            return Err(OpenSkillError::InvalidArgument(
+                "points_per_division must be positive",
+            ));
+        }
+        let points_ok = self.base_points >= 1.0 && self.max_points >= self.base_points;
+        let gap_ok = self.gap_factor >= 0.0;
+        if !points_ok || !gap_ok || !self.max_points.is_finite() || !self.gap_factor.is_finite() {
+            return Err(OpenSkillError::InvalidArgument(
+                "points need 1 <= base_points <= max_points and a non-negative gap_factor",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn tiers(&self) -> &[Tier] {
+        &self.tiers
+    }
+
+    fn check(&self, rank: &LadderRank) -> Result<()> {
+        match self.tiers.get(rank.tier) {
+            Some(tier) if rank.division < tier.divisions => Ok(()),
+            _ => Err(OpenSkillError::InvalidArgument(
+                "rank is outside of the ladder tiers",
+            )),
+        }
+    }
+
+    /// Divisions below the first division of `tier`.
+    fn divisions_before(&self, tier: usize) -> u64 {
+        self.tiers[..tier]
+            .iter()
+            .map(|it| it.divisions as u64)
+            .sum()
+    }
+
+    fn is_top_division(&self, rank: &LadderRank) -> bool {
+        rank.tier + 1 == self.tiers.len() && rank.division + 1 == self.tiers[rank.tier].divisions
+    }
+
+    /// Ladder points from the very bottom of the ladder to `rank`.
+    pub fn visible_position(&self, rank: &LadderRank) -> Result<f64> {
+        self.check(rank)?;
+        let divisions = self.divisions_before(rank.tier) + rank.division as u64;
+        Ok(divisions as f64 * self.points_per_division as f64 + rank.points as f64)
+    }
+
+    /// Where `ordinal` would sit on the ladder, in points from the very bottom. Each tier spreads
+    /// its divisions evenly up to the next tier's `min_ordinal`; the top tier keeps the rate of
+    /// the one below it and has no upper end.
+    pub fn hidden_position(&self, ordinal: f64) -> f64 {
+        let tiers = &self.tiers;
+        if ordinal.is_nan() || ordinal <= tiers[0].min_ordinal {
+            return 0.0;
+        }
+        let tier = tiers
+            .iter()
+            .rposition(|it| it.min_ordinal <= ordinal)
+            .unwrap_or(0);
+        let width = |i: usize| tiers[i].divisions as f64 * self.points_per_division as f64;
+        let rate = match tiers.get(tier + 1) {
+            Some(next) => width(tier) / (next.min_ordinal - tiers[tier].min_ordinal),
+            None => width(tier - 1) / (tiers[tier].min_ordinal - tiers[tier - 1].min_ordinal),
+        };
+        let start = self.divisions_before(tier) as f64 * self.points_per_division as f64;
+        start + (ordinal - tiers[tier].min_ordinal) * rate
+    }
+
+    fn rank_at(&self, position: f64) -> LadderRank {
+        let position = position.max(0.0).floor() as u64;
+        let per_division = self.points_per_division as u64;
+        let mut division = position / per_division;
+        for (tier, it) in self.tiers.iter().enumerate() {
+            let last = tier + 1 == self.tiers.len();
+            if division < it.divisions as u64 || last {
+                let division = division.min(it.divisions as u64 - 1);
+                let points = position - (self.divisions_before(tier) + division) * per_division;
+                return LadderRank {
+                    tier,
+                    division: division as u32,
+                    points: points.min(u32::MAX as u64) as u32,
+                    protection: 0,
+                };
+            }
+            division -= it.divisions as u64;
+        }
+        unreachable!("the last tier takes every position")
+    }
+
+    /// Initial visible rank straight from the hidden ordinal, e.g. after placement games.
+    pub fn place(&self, ordinal: f64) -> LadderRank {
+        self.rank_at(self.hidden_position(ordinal))
+    }
+
+    /// Points for a game won (`won`) or lost at visible `rank` by a player whose hidden ordinal
+    /// after the game is `ordinal`.
+    pub fn points(&self, rank: &LadderRank, ordinal: f64, won: bool) -> Result<i64> {
+        let gap = self.hidden_position(ordinal) - self.visible_position(rank)?;
+        let sign = if won { 1.0 } else { -1.0 };
+        let points = (self.base_points + sign * self.gap_factor * gap).clamp(1.0, self.max_points);
+        Ok((sign * points.round()) as i64)
+    }
+
+    /// Applies a won or lost game to `rank`; `ordinal` is the hidden `Env::ordinal` after it.
+    /// Every full division of points gained promotes and every one lost demotes, and a
+    /// promotion into a new tier protects the player from dropping out of it for a few games.
+    pub fn record(&self, rank: &LadderRank, ordinal: f64, won: bool) -> Result<LadderUpdate> {
+        let points = self.points(rank, ordinal, won)?;
+        let per_division = self.points_per_division as i64;
+        let mut next = rank.clone();
+        next.protection = next.protection.saturating_sub(1);
+        let mut total = rank.points as i64 + points;
+        let mut movement = Movement::Stayed;
+
+        while total >= per_division && !self.is_top_division(&next) {
+            total -= per_division;
+            movement = Movement::Promoted;
+            if next.division + 1 < self.tiers[next.tier].divisions {
+                next.division += 1;
+            } else {
+                next.tier += 1;
+                next.division = 0;
+                next.protection = self.protection_games;
+            }
+        }
+
+        // protection only keeps the player in the tier they start the game in
+        while total < 0 {
+            let protected = next.tier == rank.tier && rank.protection > 0;
+            if next.division > 0 {
+                next.division -= 1;
+            } else if next.tier > 0 && !protected {
+                next.tier -= 1;
+                next.division = self.tiers[next.tier].divisions - 1;
+            } else {
+                if next.tier > 0 && movement == Movement::Stayed {
+                    movement = Movement::Protected;
+                }
+                total = 0;
+                break;
+            }
+            total += per_division;
+            movement = Movement::Demoted;
+        }
+
+        next.points = total.min(u32::MAX as i64) as u32;
+        Ok(LadderUpdate {
+            rank: next,
+            points,
+            movement,
+        })
+    }
+
+    /// e.g. `Gold 2`, with division 1 the highest; tiers with a single division show only the name.
+    pub fn label(&self, rank: &LadderRank) -> Result<String> {
+        self.check(rank)?;
+        let tier = &self.tiers[rank.tier];
+        Ok(if tier.divisions == 1 {
+            tier.name.to_string()
+        } else {
+            format!("{} {}", tier.name, tier.divisions - rank.division)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{env_builder::EnvBuilder, rating::Rating};
+
+    fn rank(tier: usize, division: u32, points: u32) -> LadderRank {
+        LadderRank {
+            tier,
+            division,
+            points,
+            protection: 0,
+        }
+    }
+
+    #[test]
+    fn invalid_config_is_error() {
+        let cases = [
+            LadderBuilder::default().tiers(vec![Tier::new("Only", 0.0, 1)]),
+            LadderBuilder::default().tiers(vec![Tier::new("A", 5.0, 1), Tier::new("B", 0.0, 1)]),
+            LadderBuilder::default().tiers(vec![Tier::new("A", 0.0, 0), Tier::new("B", 5.0, 1)]),
+            LadderBuilder::default().points_per_division(0),
+            LadderBuilder::default().base_points(60.0),
+            LadderBuilder::default().gap_factor(-1.0),
+        ];
+        for builder in cases {
+            assert!(builder.build().is_err());
+        }
+    }
+
+    #[test]
+    fn ranks_outside_the_tiers_are_errors() {
+        let ladder = Ladder::default();
+        let err = OpenSkillError::InvalidArgument("rank is outside of the ladder tiers");
+        for rank in [rank(7, 0, 0), rank(5, 1, 0), rank(usize::MAX, 0, 0)] {
+            assert_eq!(ladder.visible_position(&rank).unwrap_err(), err);
+            assert_eq!(ladder.points(&rank, 0.0, true).unwrap_err(), err);
+            assert_eq!(ladder.record(&rank, 0.0, false).unwrap_err(), err);
+            assert_eq!(ladder.label(&rank).unwrap_err(), err);
+        }
+    }
+
+    #[test]
+    fn hidden_position_follows_tier_thresholds() {
+        let ladder = Ladder::default();
+        assert_eq!(ladder.hidden_position(-100.0), 0.0);
+        assert_eq!(ladder.hidden_position(5.0), 400.0);
+        assert_eq!(ladder.hidden_position(8.5), 600.0);
+        assert_eq!(ladder.hidden_position(40.0), 2100.0);
+        // the top tier keeps the rate of Master, 100 points over 7 ordinal
+        assert_eq!(ladder.hidden_position(47.0), 2200.0);
+
+        assert_eq!(ladder.place(8.5), rank(1, 2, 0));
+        assert_eq!(ladder.place(47.0), rank(6, 0, 100));
+        assert_eq!(ladder.label(&ladder.place(8.5)).unwrap(), "Silver 2");
+        assert_eq!(ladder.label(&ladder.place(47.0)).unwrap(), "Grandmaster");
+    }
+
+    #[test]
+    fn huge_ladders_do_not_overflow() {
+        let ladder = LadderBuilder::default()
+            .tiers(vec![
+                Tier::new("Low", 0.0, u32::MAX),
+                Tier::new("High", 10.0, u32::MAX),
+            ])
+            .points_per_division(u32::MAX)
+            .build()
+            .unwrap();
+        let per_tier = u32::MAX as f64 * u32::MAX as f64;
+        assert_eq!(ladder.hidden_position(10.0), per_tier);
+        assert_eq!(ladder.hidden_position(20.0), 2.0 * per_tier);
+        let top = rank(1, u32::MAX - 1, 0);
+        assert_eq!(
+            ladder.visible_position(&top).unwrap(),
+            (2.0 * u32::MAX as f64 - 1.0) * u32::MAX as f64
+        );
+    }
+
+    #[test]
+    fn points_depend_on_hidden_gap() {
+        let ladder = Ladder::default();
+        let at = rank(1, 2, 0);
+        assert_eq!(ladder.points(&at, 8.5, true).unwrap(), 20);
+        assert_eq!(ladder.points(&at, 8.5, false).unwrap(), -20);
+        // hidden rating 200 points above the visible rank
+        assert_eq!(ladder.points(&at, 12.0, true).unwrap(), 30);
+        assert_eq!(ladder.points(&at, 12.0, false).unwrap(), -10);
+        // far below: wins are worth little, losses cost the most
+        assert_eq!(ladder.points(&at, -100.0, true).unwrap(), 1);
+        assert_eq!(ladder.points(&at, -100.0, false).unwrap(), -50);
+    }
+
+    #[test]
+    fn promotion_and_demotion() {
+        let ladder = LadderBuilder::default().gap_factor(0.0).build().unwrap();
+        let up = ladder.record(&rank(1, 1, 90), 7.0, true).unwrap();
+        assert_eq!(up.movement, Movement::Promoted);
+        assert_eq!(up.rank, rank(1, 2, 10));
+
+        let down = ladder.record(&rank(1, 1, 10), 7.0, false).unwrap();
+        assert_eq!(down.movement, Movement::Demoted);
+        assert_eq!(down.rank, rank(1, 0, 90));
+
+        let bottom = ladder.record(&rank(0, 0, 5), -5.0, false).unwrap();
+        assert_eq!(bottom.movement, Movement::Stayed);
+        assert_eq!(bottom.rank, rank(0, 0, 0));
+
+        // heavy games move as many divisions down as up
+        let heavy = LadderBuilder::default()
+            .gap_factor(0.0)
+            .base_points(250.0)
+            .max_points(250.0)
+            .build()
+            .unwrap();
+        let up = heavy.record(&rank(1, 0, 50), 7.0, true).unwrap();
+        assert_eq!(up.rank, rank(1, 3, 0));
+        let down = heavy.record(&rank(1, 3, 0), 7.0, false).unwrap();
+        assert_eq!(down.movement, Movement::Demoted);
+        assert_eq!(down.rank, rank(1, 0, 50));
+        let floor = heavy.record(&rank(0, 1, 0), 7.0, false).unwrap();
+        assert_eq!(floor.rank, rank(0, 0, 0));
+        assert_eq!(floor.movement, Movement::Demoted);
+
+        let top = ladder.record(&rank(6, 0, 990), 120.0, true).unwrap();
+        assert_eq!(top.movement, Movement::Stayed);
+        assert_eq!(top.rank.points, 1010);
+    }
+
+    #[test]
+    fn tier_promotion_grants_protection() {
+        let ladder = LadderBuilder::default()
+            .protection_games(2)
+            .build()
+            .unwrap();
+        let promoted = ladder.record(&rank(0, 3, 95), 5.0, true).unwrap();
+        assert_eq!(promoted.movement, Movement::Promoted);
+        assert_eq!(promoted.rank.tier, 1);
+        assert_eq!(promoted.rank.protection, 2);
+
+        let mut current = promoted.rank;
+        for _ in 0..2 {
+            let update = ladder.record(&current, -5.0, false).unwrap();
+            assert_eq!(update.movement, Movement::Protected);
+            assert_eq!(update.rank.tier, 1);
+            current = update.rank;
+        }
+        assert_eq!(current.protection, 0);
+        let update = ladder.record(&current, -5.0, false).unwrap();
+        assert_eq!(update.movement, Movement::Demoted);
+        assert_eq!(update.rank.tier, 0);
+    }
+
+    #[test]
+    fn visible_rank_converges_to_hidden_rating() {
+        let env = EnvBuilder::default().build();
+        let ladder = Ladder::default();
+        // a strong player starting at the bottom, then matched against equals
+        let ordinal = env.ordinal(&Rating::new(40.0, 2.0));
+        let hidden = ladder.hidden_position(ordinal);
+        let mut visible = LadderRank::default();
+        for game in 0..200 {
+            visible = ladder
+                .record(&visible, ordinal, game % 2 == 0)
+                .unwrap()
+                .rank;
+        }
+        let gap = ladder.visible_position(&visible).unwrap() - hidden;
+        assert!(gap.abs() < 25.0, "{gap}");
+        assert_eq!(ladder.label(&visible).unwrap(), "Master");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ladder_state_round_trips() {
+        let ladder = Ladder::default();
+        let state = LadderRank {
+            protection: 2,
+            ..rank(3, 1, 42)
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<LadderRank>(&json).unwrap(), state);
+
+        let restored: Ladder =
+            serde_json::from_str(&serde_json::to_string(&ladder).unwrap()).unwrap();
+        assert_eq!(restored.tiers(), ladder.tiers());
+
+        // deserializing goes through the same checks as the builder
+        let single = r#"{"tiers": [{"name": "Only", "min_ordinal": 0.0, "divisions": 1}]}"#;
+        assert!(serde_json::from_str::<Ladder>(single).is_err());
+        let empty = r#"{"tiers": [
+            {"name": "A", "min_ordinal": 0.0, "divisions": 0},
+            {"name": "B", "min_ordinal": 5.0, "divisions": 1}
+        ]}"#;
+        assert!(serde_json::from_str::<Ladder>(empty).is_err());
+        assert!(serde_json::from_str::<Ladder>("{}").is_ok());
+    }
+}
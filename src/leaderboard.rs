@@ -0,0 +1,422 @@
+use crate::rating::{OrdinalFunc, Rating};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::cmp::Ordering;
+
+#[derive(Debug, Clone)]
+pub struct Standing<K> {
+    pub id: K,
+    /// 1 based position among ranked players.
+    pub rank: usize,
+    pub ordinal: f64,
+    pub rating: Rating,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    rating: Rating,
+    ordinal: f64,
+    provisional: bool,
+}
+
+/// Players ranked by ordinal, best first; equal ordinals are ordered by id so every player
+/// has a distinct, stable rank. Updates and rank/position lookups take `O(log n)`.
+#[derive(Debug, Clone)]
+pub struct Leaderboard<K> {
+    entries: BTreeMap<K, Entry>,
+    tree: Treap<K>,
+    z: f64,
+    ordinal: OrdinalFunc,
+    exclude_provisional: bool,
+}
+
+impl<K: Ord + Clone> Leaderboard<K> {
+    pub fn new(z: f64, ordinal: OrdinalFunc) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            tree: Treap::default(),
+            z,
+            ordinal,
+            exclude_provisional: false,
+        }
+    }
+
+    /// Keeps provisional players out of the ranking; they are still tracked and enter it
+    /// once they are updated as established. Players already on the board are dropped from
+    /// the ranking if they are provisional.
+    pub fn exclude_provisional(mut self) -> Self {
+        self.exclude_provisional = true;
+        for (id, entry) in &self.entries {
+            if entry.provisional {
+                self.tree.remove(&(entry.ordinal, id.clone()));
+            }
+        }
+        self
+    }
+
+    fn is_ranked(&self, entry: &Entry) -> bool {
+        !(self.exclude_provisional && entry.provisional)
+    }
+
+    /// Inserts `id` or replaces its rating.
+    pub fn update(&mut self, id: K, rating: &Rating, provisional: bool) {
+        self.remove(&id);
+        let entry = Entry {
+            rating: rating.clone(),
+            ordinal: (self.ordinal)(rating, self.z),
+            provisional,
+        };
+        if self.is_ranked(&entry) {
+            self.tree.insert((entry.ordinal, id.clone()));
+        }
+        self.entries.insert(id, entry);
+    }
+
+    pub fn remove(&mut self, id: &K) -> Option<Rating> {
+        let entry = self.entries.remove(id)?;
+        if self.is_ranked(&entry) {
+            self.tree.remove(&(entry.ordinal, id.clone()));
+        }
+        Some(entry.rating)
+    }
+
+    /// Number of ranked players.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `None` for unknown players and, when excluded, provisional ones.
+    pub fn rank(&self, id: &K) -> Option<usize> {
+        let entry = self.entries.get(id)?;
+        if !self.is_ranked(entry) {
+            return None;
+        }
+        Some(self.tree.count_before(&(entry.ordinal, id.clone())) + 1)
+    }
+
+    pub fn get(&self, id: &K) -> Option<Standing<K>> {
+        let rank = self.rank(id)?;
+        Some(self.standing(id.clone(), rank))
+    }
+
+    /// Share of ranked players, in percent, that are at or below `id`: 100.0 for the leader.
+    pub fn percentile(&self, id: &K) -> Option<f64> {
+        let rank = self.rank(id)?;
+        Some((self.len() - rank + 1) as f64 / self.len() as f64 * 100.0)
+    }
+
+    pub fn top(&self, n: usize) -> Vec<Standing<K>> {
+        self.range(0, n)
+    }
+
+    /// Page `page` (0 based) of `size` players.
+    pub fn page(&self, page: usize, size: usize) -> Vec<Standing<K>> {
+        self.range(page.saturating_mul(size), size)
+    }
+
+    /// Up to `len` players starting at the 0 based position `start`.
+    pub fn range(&self, start: usize, len: usize) -> Vec<Standing<K>> {
+        (start..self.len().min(start.saturating_add(len)))
+            .map(|i| {
+                let (_, id) = self.tree.select(i);
+                self.standing(id.clone(), i + 1)
+            })
+            .collect()
+    }
+
+    fn standing(&self, id: K, rank: usize) -> Standing<K> {
+        let entry = &self.entries[&id];
+        Standing {
+            rank,
+            ordinal: entry.ordinal,
+            rating: entry.rating.clone(),
+            id,
+        }
+    }
+}
+
+type Key<K> = (f64, K);
+
+// higher ordinal first, then lower id
+fn compare<K: Ord>(a: &Key<K>, b: &Key<K>) -> Ordering {
+    b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1))
+}
+
+#[derive(Debug, Clone)]
+struct Node<K> {
+    key: Key<K>,
+    priority: u64,
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Treap with subtree sizes over an arena, so rank and select follow one root-to-leaf path.
+#[derive(Debug, Clone)]
+struct Treap<K> {
+    nodes: Vec<Node<K>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    seed: u64,
+}
+
+impl<K> Default for Treap<K> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+}
+
+impl<K: Ord> Treap<K> {
+    fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |it| self.nodes[it].size)
+    }
+
+    fn fix(&mut self, node: usize) {
+        let Node { left, right, .. } = self.nodes[node];
+        self.nodes[node].size = self.size(left) + self.size(right) + 1;
+    }
+
+    // xorshift64, deterministic so equal inputs build equal trees
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    /// Splits into keys before `key` and the rest; with `inclusive` `key` itself goes left.
+    fn split(
+        &mut self,
+        node: Option<usize>,
+        key: &Key<K>,
+        inclusive: bool,
+    ) -> (Option<usize>, Option<usize>) {
+        let Some(n) = node else {
+            return (None, None);
+        };
+        let goes_left = match compare(&self.nodes[n].key, key) {
+            Ordering::Less => true,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => false,
+        };
+        if goes_left {
+            let (l, r) = self.split(self.nodes[n].right, key, inclusive);
+            self.nodes[n].right = l;
+            self.fix(n);
+            (Some(n), r)
+        } else {
+            let (l, r) = self.split(self.nodes[n].left, key, inclusive);
+            self.nodes[n].left = r;
+            self.fix(n);
+            (l, Some(n))
+        }
+    }
+
+    fn merge(&mut self, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        match (a, b) {
+            (None, it) | (it, None) => it,
+            (Some(a), Some(b)) => {
+                if self.nodes[a].priority > self.nodes[b].priority {
+                    let right = self.merge(self.nodes[a].right, Some(b));
+                    self.nodes[a].right = right;
+                    self.fix(a);
+                    Some(a)
+                } else {
+                    let left = self.merge(Some(a), self.nodes[b].left);
+                    self.nodes[b].left = left;
+                    self.fix(b);
+                    Some(b)
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, key: Key<K>) {
+        let (l, r) = self.split(self.root, &key, false);
+        let node = Node {
+            key,
+            priority: self.next_priority(),
+            size: 1,
+            left: None,
+            right: None,
+        };
+        let n = match self.free.pop() {
+            Some(n) => {
+                self.nodes[n] = node;
+                n
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        let l = self.merge(l, Some(n));
+        self.root = self.merge(l, r);
+    }
+
+    fn remove(&mut self, key: &Key<K>) {
+        let (l, rest) = self.split(self.root, key, false);
+        let (found, r) = self.split(rest, key, true);
+        if let Some(n) = found {
+            self.free.push(n);
+        }
+        self.root = self.merge(l, r);
+    }
+
+    fn count_before(&self, key: &Key<K>) -> usize {
+        let mut count = 0;
+        let mut node = self.root;
+        while let Some(n) = node {
+            match compare(&self.nodes[n].key, key) {
+                Ordering::Less => {
+                    count += self.size(self.nodes[n].left) + 1;
+                    node = self.nodes[n].right;
+                }
+                Ordering::Equal => return count + self.size(self.nodes[n].left),
+                Ordering::Greater => node = self.nodes[n].left,
+            }
+        }
+        count
+    }
+
+    /// Key at 0 based `index`, which must be below `len()`.
+    fn select(&self, mut index: usize) -> &Key<K> {
+        let mut node = self.root;
+        while let Some(n) = node {
+            let left = self.size(self.nodes[n].left);
+            match index.cmp(&left) {
+                Ordering::Less => node = self.nodes[n].left,
+                Ordering::Equal => return &self.nodes[n].key,
+                Ordering::Greater => {
+                    index -= left + 1;
+                    node = self.nodes[n].right;
+                }
+            }
+        }
+        panic!("index out of range")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{constant::DEFAULT_Z, rating::default_ordinal};
+    use alloc::vec;
+
+    fn board() -> Leaderboard<u32> {
+        Leaderboard::new(DEFAULT_Z, default_ordinal)
+    }
+
+    fn ids(standings: &[Standing<u32>]) -> Vec<u32> {
+        standings.iter().map(|it| it.id).collect()
+    }
+
+    #[test]
+    fn matches_full_sort_under_updates() {
+        let mut board = board();
+        let mut expected: BTreeMap<u32, f64> = BTreeMap::new();
+        let mut seed = 42u64;
+        for step in 0..3000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let id = (seed >> 33) as u32 % 200;
+            if step % 7 == 0 {
+                board.remove(&id);
+                expected.remove(&id);
+                continue;
+            }
+            // coarse ordinals so exact ties are common
+            let mu = ((seed >> 20) % 40) as f64;
+            let rating = Rating::new(mu, 1.0);
+            board.update(id, &rating, false);
+            expected.insert(id, default_ordinal(&rating, DEFAULT_Z));
+        }
+
+        let mut sorted: Vec<(f64, u32)> = expected.iter().map(|(id, o)| (*o, *id)).collect();
+        sorted.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        assert_eq!(board.len(), sorted.len());
+        assert_eq!(
+            ids(&board.top(usize::MAX)),
+            sorted.iter().map(|it| it.1).collect::<Vec<_>>()
+        );
+        for (i, (ordinal, id)) in sorted.iter().enumerate() {
+            assert_eq!(board.rank(id), Some(i + 1));
+            assert_eq!(board.get(id).unwrap().ordinal, *ordinal);
+        }
+    }
+
+    #[test]
+    fn ties_are_ordered_by_id() {
+        let mut board = board();
+        for id in [3, 1, 2] {
+            board.update(id, &Rating::new(25.0, 5.0), false);
+        }
+        board.update(9, &Rating::new(30.0, 5.0), false);
+        assert_eq!(ids(&board.top(10)), vec![9, 1, 2, 3]);
+        assert_eq!(board.rank(&3), Some(4));
+    }
+
+    #[test]
+    fn pages_and_percentiles() {
+        let mut board = board();
+        for id in 0..10 {
+            board.update(id, &Rating::new(id as f64, 1.0), false);
+        }
+        assert_eq!(ids(&board.top(3)), vec![9, 8, 7]);
+        assert_eq!(ids(&board.page(1, 4)), vec![5, 4, 3, 2]);
+        assert_eq!(ids(&board.page(2, 4)), vec![1, 0]);
+        assert!(board.page(3, 4).is_empty());
+        assert_eq!(board.percentile(&9), Some(100.0));
+        assert_eq!(board.percentile(&0), Some(10.0));
+        assert_eq!(board.percentile(&42), None);
+    }
+
+    #[test]
+    fn provisional_players_can_be_excluded() {
+        let mut board = board().exclude_provisional();
+        board.update(1, &Rating::new(40.0, 8.0), true);
+        board.update(2, &Rating::new(20.0, 1.0), false);
+        assert_eq!(board.len(), 1);
+        assert_eq!(board.rank(&1), None);
+        assert_eq!(board.rank(&2), Some(1));
+
+        board.update(1, &Rating::new(40.0, 2.0), false);
+        assert_eq!(ids(&board.top(2)), vec![1, 2]);
+        assert!(board.remove(&1).is_some());
+        assert_eq!(board.len(), 1);
+
+        let mut all = Leaderboard::new(DEFAULT_Z, default_ordinal);
+        all.update(1, &Rating::new(40.0, 8.0), true);
+        assert_eq!(all.rank(&1), Some(1));
+    }
+
+    #[test]
+    fn excluding_after_updates_drops_provisional_players() {
+        let mut board = board();
+        board.update(1, &Rating::new(40.0, 8.0), true);
+        board.update(2, &Rating::new(20.0, 1.0), false);
+        board.update(3, &Rating::new(30.0, 8.0), true);
+        let mut board = board.exclude_provisional();
+        assert_eq!(board.len(), 1);
+        assert_eq!(ids(&board.top(10)), vec![2]);
+        assert_eq!(board.rank(&1), None);
+
+        // removing a player that was dropped must not touch the tree
+        assert!(board.remove(&3).is_some());
+        board.update(1, &Rating::new(40.0, 2.0), false);
+        assert_eq!(ids(&board.top(10)), vec![1, 2]);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    env::Env,
+    error::{OpenSkillError, Result},
+    rating::{GameResult, Rating},
+    storage::MatchRecord,
+    validate::validate_match,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+#[derive(Debug, Clone)]
+struct Entry {
+    id: u64,
+    record: MatchRecord,
+    voided: bool,
+    /// Ratings of the players of `record` coming out of the match.
+    after: Vec<Vec<Rating>>,
+}
+
+/// An append-only log of matches and the ratings they produce. Past matches can be voided or
+/// amended; only the matches after it that share players with it, directly or through other
+/// recomputed matches, are rated again.
+pub struct Ledger {
+    env: Env,
+    entries: Vec<Entry>,
+    ratings: BTreeMap<String, Rating>,
+}
+
+impl Ledger {
+    pub fn new(env: Env) -> Self {
+        Self {
+            env,
+            entries: Vec::new(),
+            ratings: BTreeMap::new(),
+        }
+    }
+
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Current rating of `id`, `None` if they have no counted matches.
+    pub fn rating(&self, id: &str) -> Option<&Rating> {
+        self.ratings.get(id)
+    }
+
+    pub fn ratings(&self) -> &BTreeMap<String, Rating> {
+        &self.ratings
+    }
+
+    /// Every match in the order it was recorded, with whether it is voided.
+    pub fn matches(&self) -> impl Iterator<Item = (u64, &MatchRecord, bool)> {
+        self.entries.iter().map(|it| (it.id, &it.record, it.voided))
+    }
+
+    /// Rates `record` with the current ratings and appends it. Returns the match id and the
+    /// new ratings of its players.
+    pub fn record(&mut self, record: MatchRecord) -> Result<(u64, Vec<Vec<Rating>>)> {
+        let before = self.current(&record);
+        let after = self.rate(&record, &before)?;
+        let id = self.entries.last().map_or(1, |it| it.id + 1);
+        for (name, rating) in record.teams.iter().flatten().zip(after.iter().flatten()) {
+            self.ratings.insert(name.clone(), rating.clone());
+        }
+        self.entries.push(Entry {
+            id,
+            record,
+            voided: false,
+            after: after.clone(),
+        });
+        Ok((id, after))
+    }
+
+    /// Removes match `id` from the ratings. Returns the players whose rating changed; players
+    /// left without any counted match are reported with the default rating.
+    pub fn void(&mut self, id: u64) -> Result<BTreeMap<String, Rating>> {
+        self.replace(id, None)
+    }
+
+    /// Replaces the result of match `id` with `record`. Returns the players whose rating changed.
+    pub fn amend(&mut self, id: u64, record: MatchRecord) -> Result<BTreeMap<String, Rating>> {
+        self.replace(id, Some(record))
+    }
+
+    fn current(&self, record: &MatchRecord) -> Vec<Vec<Rating>> {
+        record
+            .teams
+            .iter()
+            .map(|team| {
+                team.iter()
+                    .map(|it| {
+                        self.ratings
+                            .get(it)
+                            .cloned()
+                            .unwrap_or_else(|| self.env.new_rating())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn rate(&self, record: &MatchRecord, teams: &[Vec<Rating>]) -> Result<Vec<Vec<Rating>>> {
+        validate_match(&record.teams, &record.ranks)?;
+        self.env
+            .rate(&GameResult::new(teams.to_vec(), record.ranks.clone()).weight(record.weight))
+    }
+
+    /// Rating of `player` going into entry `index`, from the last counted entry before it.
+    fn rating_before(&self, index: usize, player: &str) -> Option<Rating> {
+        self.entries[..index]
+            .iter()
+            .rev()
+            .filter(|it| !it.voided)
+            .find_map(|it| {
+                let names = it.record.teams.iter().flatten();
+                names
+                    .zip(it.after.iter().flatten())
+                    .find(|(name, _)| *name == player)
+                    .map(|(_, rating)| rating.clone())
+            })
+    }
+
+    fn replace(
+        &mut self,
+        id: u64,
+        record: Option<MatchRecord>,
+    ) -> Result<BTreeMap<String, Rating>> {
+        let index = self
+            .entries
+            .binary_search_by_key(&id, |it| it.id)
+            .map_err(|_| OpenSkillError::InvalidArgument("unknown match"))?;
+        if self.entries[index].voided {
+            return Err(OpenSkillError::InvalidArgument("match is voided"));
+        }
+
+        // working ratings of every affected player, as of the entry being recomputed
+        let mut working: BTreeMap<String, Option<Rating>> = BTreeMap::new();
+        let touch = |working: &mut BTreeMap<String, Option<Rating>>, at: usize, name: &String| {
+            if !working.contains_key(name) {
+                working.insert(name.clone(), self.rating_before(at, name));
+            }
+        };
+        for name in self.entries[index].record.teams.iter().flatten() {
+            touch(&mut working, index, name);
+        }
+
+        let mut updates = Vec::new();
+        let replaced = match &record {
+            Some(record) => {
+                for name in record.teams.iter().flatten() {
+                    touch(&mut working, index, name);
+                }
+                Some(record)
+            }
+            None => None,
+        };
+        let entries = core::iter::once((index, replaced))
+            .chain((index + 1..self.entries.len()).map(|it| (it, None)));
+        for (at, replaced) in entries {
+            let entry = &self.entries[at];
+            let record = match replaced {
+                Some(record) => record,
+                None if at == index || entry.voided => continue,
+                None => &entry.record,
+            };
+            let names = record.teams.iter().flatten();
+            if at != index && !names.clone().any(|it| working.contains_key(it)) {
+                continue;
+            }
+            for name in names {
+                touch(&mut working, at, name);
+            }
+
+            let before: Vec<Vec<Rating>> = record
+                .teams
+                .iter()
+                .map(|team| {
+                    team.iter()
+                        .map(|it| working[it].clone().unwrap_or_else(|| self.env.new_rating()))
+                        .collect()
+                })
+                .collect();
+            let after = self.rate(record, &before)?;
+            for (name, rating) in record.teams.iter().flatten().zip(after.iter().flatten()) {
+                working.insert(name.clone(), Some(rating.clone()));
+            }
+            updates.push((at, after));
+        }
+
+        // everything is rated, commit
+        let entry = &mut self.entries[index];
+        match record {
+            Some(record) => entry.record = record,
+            None => entry.voided = true,
+        }
+        for (at, after) in updates {
+            self.entries[at].after = after;
+        }
+
+        let mut changed = BTreeMap::new();
+        for (name, rating) in working {
+            let old = self.ratings.get(&name);
+            let same = match (&rating, old) {
+                (Some(new), Some(old)) => new.mu == old.mu && new.sigma == old.sigma,
+                (None, None) => true,
+                _ => false,
+            };
+            if same {
+                continue;
+            }
+            match rating {
+                Some(rating) => {
+                    self.ratings.insert(name.clone(), rating.clone());
+                    changed.insert(name, rating);
+                }
+                None => {
+                    self.ratings.remove(&name);
+                    changed.insert(name, self.env.new_rating());
+                }
+            }
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{env_builder::EnvBuilder, storage::test::record};
+    use alloc::vec;
+
+    fn ledger(records: &[MatchRecord]) -> Ledger {
+        let mut ledger = Ledger::new(EnvBuilder::default().build());
+        for it in records {
+            ledger.record(it.clone()).unwrap();
+        }
+        ledger
+    }
+
+    fn history() -> Vec<MatchRecord> {
+        vec![
+            record(&[&["alice"], &["bob"]], vec![1, 2]),
+            record(&[&["carol"], &["dave"]], vec![1, 2]),
+            record(&[&["bob"], &["carol"]], vec![1, 2]),
+            record(&[&["erin"], &["frank"]], vec![2, 1]),
+            record(&[&["alice", "dave"], &["carol"]], vec![2, 1]),
+        ]
+    }
+
+    fn assert_same_ratings(got: &Ledger, want: &Ledger) {
+        assert_eq!(
+            got.ratings().keys().collect::<Vec<_>>(),
+            want.ratings().keys().collect::<Vec<_>>()
+        );
+        for (name, rating) in want.ratings() {
+            let it = got.rating(name).unwrap();
+            assert_eq!((it.mu, it.sigma), (rating.mu, rating.sigma), "{name}");
+        }
+    }
+
+    #[test]
+    fn void_matches_replay_without_the_match() {
+        let mut got = ledger(&history());
+        let changed = got.void(1).unwrap();
+
+        let mut without = history();
+        without.remove(0);
+        assert_same_ratings(&got, &ledger(&without));
+        // alice is left with the last match only, erin and frank never shared a match
+        assert!(changed.contains_key("alice"));
+        assert!(changed.contains_key("dave"));
+        assert!(!changed.contains_key("erin"));
+        assert!(!changed.contains_key("frank"));
+        assert_eq!(got.matches().filter(|(_, _, voided)| *voided).count(), 1);
+    }
+
+    #[test]
+    fn amend_matches_replay_with_the_new_result() {
+        let mut got = ledger(&history());
+        let amended = record(&[&["carol"], &["dave"], &["gina"]], vec![3, 1, 2]);
+        let changed = got.amend(2, amended.clone()).unwrap();
+
+        let mut want = history();
+        want[1] = amended;
+        assert_same_ratings(&got, &ledger(&want));
+        assert!(changed.contains_key("gina"));
+        assert!(!changed.contains_key("erin"));
+        assert_eq!(got.matches().nth(1).unwrap().1.teams.len(), 3);
+    }
+
+    #[test]
+    fn voiding_the_only_match_drops_players() {
+        let mut got = ledger(&history()[..1]);
+        let changed = got.void(1).unwrap();
+        assert!(got.ratings().is_empty());
+        assert_eq!(changed["bob"].mu, got.env().new_rating().mu);
+
+        // later matches keep their ids and rate from scratch
+        let (id, _) = got.record(history()[0].clone()).unwrap();
+        assert_eq!(id, 2);
+        assert_same_ratings(&got, &ledger(&history()[..1]));
+    }
+
+    #[test]
+    fn invalid_changes_leave_the_ledger_untouched() {
+        let mut got = ledger(&history());
+        assert_eq!(
+            got.void(42).unwrap_err(),
+            OpenSkillError::InvalidArgument("unknown match")
+        );
+        let twice = record(&[&["alice"], &["alice"]], vec![1, 2]);
+        assert!(got.amend(3, twice).is_err());
+        assert_same_ratings(&got, &ledger(&history()));
+        assert_eq!(got.matches().nth(2).unwrap().1, &history()[2]);
+
+        got.void(3).unwrap();
+        assert_eq!(
+            got.amend(3, history()[2].clone()).unwrap_err(),
+            OpenSkillError::InvalidArgument("match is voided")
+        );
+    }
+}
@@ -1,21 +1,51 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod advantage;
+mod batch;
 pub mod constant;
 pub mod env;
 pub mod env_builder;
 pub mod error;
+pub mod explain;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod import;
+pub mod items;
+pub mod ladder;
+pub mod leaderboard;
+pub mod ledger;
 mod math;
 pub mod model;
 pub mod predict_draw;
+pub mod predict_outcome;
 pub mod predict_win;
+pub mod preview;
+pub mod provisional;
+#[cfg(feature = "python")]
+pub mod python;
 pub mod rating;
+pub mod scale;
+pub mod smooth;
+pub mod soft_reset;
+pub mod storage;
 mod utils;
-mod validate;
+pub mod validate;
 
 pub mod prelude {
     pub use crate::{
         env::Env,
         env_builder::EnvBuilder,
         error::OpenSkillError,
+        items::{ItemRanking, Judgment},
+        ladder::{Ladder, LadderBuilder, LadderRank},
+        leaderboard::Leaderboard,
+        ledger::Ledger,
         model::kind::ModelKind,
+        provisional::{ProvisionalPolicy, RatingStatus},
         rating::{GameResult, Rating},
+        scale::{RatingScale, RatingScaleBuilder},
+        soft_reset::SoftResetPolicy,
     };
 }
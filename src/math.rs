@@ -1,7 +1,83 @@
-use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use core::f64::consts::{PI, SQRT_2};
+
+/// Standard normal distribution built on `libm`, so it is available without `std`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Normal;
+
+impl Normal {
+    pub(crate) fn pdf(&self, x: f64) -> f64 {
+        (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+    }
+
+    pub(crate) fn cdf(&self, x: f64) -> f64 {
+        0.5 * libm::erfc(-x / SQRT_2)
+    }
+
+    /// Acklam's rational approximation, polished with one Halley step to full precision.
+    pub(crate) fn inverse_cdf(&self, p: f64) -> f64 {
+        if p <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if p >= 1.0 {
+            return f64::INFINITY;
+        }
+        const A: [f64; 6] = [
+            -3.969683028665376e1,
+            2.209460984245205e2,
+            -2.759285104469687e2,
+            1.38357751867269e2,
+            -3.066479806614716e1,
+            2.506628277459239e0,
+        ];
+        const B: [f64; 5] = [
+            -5.447609879822406e1,
+            1.615858368580409e2,
+            -1.556989798598866e2,
+            6.680131188771972e1,
+            -1.328068155288572e1,
+        ];
+        const C: [f64; 6] = [
+            -7.784894002430293e-3,
+            -3.223964580411365e-1,
+            -2.400758277161838e0,
+            -2.549732539343734e0,
+            4.374664141464968e0,
+            2.938163982698783e0,
+        ];
+        const D: [f64; 4] = [
+            7.784695709041462e-3,
+            3.224671290700398e-1,
+            2.445134137142996e0,
+            3.754408661907416e0,
+        ];
+        const P_LOW: f64 = 0.02425;
+
+        let tail = |q: f64| {
+            (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+                / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+        };
+        let x = if p < P_LOW {
+            tail((-2.0 * p.ln()).sqrt())
+        } else if p > 1.0 - P_LOW {
+            -tail((-2.0 * (1.0 - p).ln()).sqrt())
+        } else {
+            let q = p - 0.5;
+            let r = q * q;
+            (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+                / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+        };
+
+        let e = self.cdf(x) - p;
+        let u = e * (2.0 * PI).sqrt() * (0.5 * x * x).exp();
+        x - u / (1.0 + 0.5 * x * u)
+    }
+}
 
 pub(crate) fn normal() -> Normal {
-    Normal::new(0.0, 1.0).unwrap()
+    Normal
 }
 
 pub(crate) fn draw_margin(num_teams: usize, total_player: usize, beta: f64) -> f64 {
@@ -78,3 +154,51 @@ pub(crate) fn wt(x: f64, t: f64) -> f64 {
             + vt(x, t) * vt(x, t)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::relative_eq;
+
+    #[test]
+    fn normal_matches_reference_values() {
+        assert!(relative_eq!(normal().cdf(0.0), 0.5));
+        assert!(relative_eq!(
+            normal().cdf(1.0),
+            0.8413447460685429,
+            epsilon = 1e-15
+        ));
+        assert!(relative_eq!(
+            normal().cdf(-3.0),
+            0.0013498980316301,
+            epsilon = 1e-15
+        ));
+        assert!(relative_eq!(
+            normal().pdf(1.0),
+            0.24197072451914337,
+            epsilon = 1e-15
+        ));
+        assert!(relative_eq!(
+            normal().inverse_cdf(0.75),
+            0.6744897501960817,
+            epsilon = 1e-14
+        ));
+        assert!(relative_eq!(
+            normal().inverse_cdf(0.001),
+            -3.090232306167813,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[test]
+    fn inverse_cdf_round_trips() {
+        for i in 1..1000 {
+            let p = i as f64 / 1000.0;
+            assert!(relative_eq!(
+                normal().cdf(normal().inverse_cdf(p)),
+                p,
+                epsilon = 1e-15
+            ));
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use crate::{
+    explain::{OpponentContribution, TeamExplanation},
     math::score,
-    rating::{to_team_ratings, GammaFunc, Rating},
-    utils::{update_team_rating, zip_without_self},
+    rating::{sum_aggregate, AggregateFunc, GameResult, GammaFunc},
 };
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 use super::model::Model;
 
 pub struct BradleyTerryFull {
+    aggregate: AggregateFunc,
     gamma: GammaFunc,
     epsilon: f64,
     two_beta_sq: f64,
@@ -15,32 +20,51 @@ pub struct BradleyTerryFull {
 impl BradleyTerryFull {
     pub fn new(beta: f64, epsilon: f64, gamma: GammaFunc) -> Self {
         Self {
+            aggregate: sum_aggregate,
             gamma,
             epsilon,
             two_beta_sq: 2.0 * beta.powi(2),
         }
     }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
 }
 
 impl Model for BradleyTerryFull {
-    fn rate(&self, teams: Vec<Vec<Rating>>, ranks: Vec<usize>) -> Vec<Vec<Rating>> {
-        let team_ratings = to_team_ratings(&teams, Some(ranks));
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let team_ratings = result.team_ratings(self.aggregate);
         let num_teams = team_ratings.len() as f64;
 
-        zip_without_self(&team_ratings)
-            .map(|(team_i, others)| {
-                let (omega, delta) = others.iter().fold((0., 0.), |(omega, delta), team_q| {
-                    let ciq = (team_i.sigma_sq + team_q.sigma_sq + self.two_beta_sq).sqrt();
-                    let piq = 1.0 / (1.0 + ((team_q.mu - team_i.mu) / ciq).exp());
-                    let sigma_sq_to_ciq = team_i.sigma_sq / ciq;
-                    let i_gamma = (self.gamma)(ciq, num_teams, team_i);
-                    (
-                        omega + sigma_sq_to_ciq * (score(team_q.rank, team_i.rank) - piq),
-                        delta + ((i_gamma * sigma_sq_to_ciq) / ciq) * piq * (1.0 - piq),
-                    )
-                });
+        team_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, team_i)| {
+                let contributions = (0..team_ratings.len())
+                    .filter(|q| *q != i)
+                    .map(|q| {
+                        let team_q = &team_ratings[q];
+                        let ciq = (team_i.sigma_sq + team_q.sigma_sq + self.two_beta_sq).sqrt();
+                        let piq = 1.0 / (1.0 + ((team_q.mu - team_i.mu) / ciq).exp());
+                        let sigma_sq_to_ciq = team_i.sigma_sq / ciq;
+                        let i_gamma = (self.gamma)(ciq, num_teams, team_i);
+                        OpponentContribution {
+                            opponent: q,
+                            omega: result.weight
+                                * sigma_sq_to_ciq
+                                * (score(team_q.rank, team_i.rank) - piq),
+                            delta: result.weight
+                                * ((i_gamma * sigma_sq_to_ciq) / ciq)
+                                * piq
+                                * (1.0 - piq),
+                            gamma: i_gamma,
+                        }
+                    })
+                    .collect();
 
-                update_team_rating(team_i, omega, delta, self.epsilon)
+                TeamExplanation::new(team_i, contributions, self.epsilon)
             })
             .collect()
     }
@@ -1,13 +1,17 @@
 use crate::{
+    explain::{OpponentContribution, TeamExplanation},
     math::score,
-    rating::{ladder_pairs, to_team_ratings, GammaFunc, Rating},
-    utils::update_team_rating,
+    rating::{ladder_pairs, sum_aggregate, AggregateFunc, GameResult, GammaFunc},
 };
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 use super::model::Model;
-use std::iter::zip;
 
 pub struct BradleyTerryPart {
+    aggregate: AggregateFunc,
     two_beta_sq: f64,
     kappa: f64,
     gamma: GammaFunc,
@@ -16,32 +20,53 @@ pub struct BradleyTerryPart {
 impl BradleyTerryPart {
     pub fn new(beta: f64, kappa: f64, gamma: GammaFunc) -> Self {
         Self {
+            aggregate: sum_aggregate,
             two_beta_sq: 2.0 * beta.powi(2),
             kappa,
             gamma,
         }
     }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
 }
 
 impl Model for BradleyTerryPart {
-    fn rate(&self, teams: Vec<Vec<Rating>>, ranks: Vec<usize>) -> Vec<Vec<Rating>> {
-        let team_ratings = to_team_ratings(&teams, Some(ranks));
-        let adjacent_teams = ladder_pairs(&team_ratings);
-
-        zip(&team_ratings, adjacent_teams)
-            .map(|(team_i, adj)| {
-                let (omega, delta) = adj.iter().fold((0., 0.), |(omega, delta), team_q| {
-                    let ciq = (team_i.sigma_sq + team_q.sigma_sq + self.two_beta_sq).sqrt();
-                    let piq = 1.0 / (1.0 + ((team_q.mu - team_i.mu) / ciq).exp());
-                    let sigma_sq_to_ciq = team_i.sigma_sq / ciq;
-                    let i_gamma = (self.gamma)(ciq, team_ratings.len() as f64, &team_i);
-                    (
-                        omega + sigma_sq_to_ciq * (score(team_q.rank, team_i.rank) - piq),
-                        delta + ((i_gamma * sigma_sq_to_ciq) / ciq) * piq * (1.0 - piq),
-                    )
-                });
-
-                update_team_rating(team_i, omega, delta, self.kappa)
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let team_ratings = result.team_ratings(self.aggregate);
+        let num_teams = team_ratings.len() as f64;
+        let adjacent = ladder_pairs(&(0..team_ratings.len()).collect::<Vec<_>>());
+
+        team_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, team_i)| {
+                let contributions = adjacent[i]
+                    .iter()
+                    .copied()
+                    .map(|q| {
+                        let team_q = &team_ratings[q];
+                        let ciq = (team_i.sigma_sq + team_q.sigma_sq + self.two_beta_sq).sqrt();
+                        let piq = 1.0 / (1.0 + ((team_q.mu - team_i.mu) / ciq).exp());
+                        let sigma_sq_to_ciq = team_i.sigma_sq / ciq;
+                        let i_gamma = (self.gamma)(ciq, num_teams, team_i);
+                        OpponentContribution {
+                            opponent: q,
+                            omega: result.weight
+                                * sigma_sq_to_ciq
+                                * (score(team_q.rank, team_i.rank) - piq),
+                            delta: result.weight
+                                * ((i_gamma * sigma_sq_to_ciq) / ciq)
+                                * piq
+                                * (1.0 - piq),
+                            gamma: i_gamma,
+                        }
+                    })
+                    .collect();
+
+                TeamExplanation::new(team_i, contributions, self.kappa)
             })
             .collect()
     }
@@ -0,0 +1,184 @@
+use crate::{
+    constant::KAPPA,
+    explain::{OpponentContribution, TeamExplanation},
+    math::score,
+    rating::{sum_aggregate, AggregateFunc, GameResult},
+};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use super::model::Model;
+
+/// Classic Elo, for comparison with the Weng-Lin models. `mu` is the Elo rating and `sigma` is
+/// never updated; it only sets how a team's change is split between teammates, so keep it
+/// equal and positive. With more than two teams every pair of teams is an Elo game of its own.
+pub struct Elo {
+    aggregate: AggregateFunc,
+    k: f64,
+}
+
+impl Elo {
+    pub fn new(k: f64) -> Self {
+        Self {
+            aggregate: sum_aggregate,
+            k,
+        }
+    }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Expected score of a player rated `a` against one rated `b`.
+    pub fn expected(a: f64, b: f64) -> f64 {
+        1.0 / (1.0 + 10f64.powf((b - a) / 400.0))
+    }
+}
+
+impl Default for Elo {
+    fn default() -> Self {
+        Self::new(32.0)
+    }
+}
+
+impl Model for Elo {
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let team_ratings = result.team_ratings(self.aggregate);
+
+        team_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, team_i)| {
+                let contributions = (0..team_ratings.len())
+                    .filter(|q| *q != i)
+                    .map(|q| {
+                        let team_q = &team_ratings[q];
+                        let expected = Self::expected(team_i.mu, team_q.mu);
+                        OpponentContribution {
+                            opponent: q,
+                            omega: result.weight
+                                * self.k
+                                * (score(team_q.rank, team_i.rank) - expected),
+                            delta: 0.0,
+                            gamma: 1.0,
+                        }
+                    })
+                    .collect();
+
+                TeamExplanation::new(team_i, contributions, KAPPA)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        env::Env, env_builder::EnvBuilder, model::kind::ModelKind, rating::default_ordinal,
+        rating::Rating,
+    };
+    use alloc::{boxed::Box, vec};
+    use approx::relative_eq;
+
+    fn env() -> Env {
+        Env::new(
+            Box::new(Elo::default()),
+            3.0,
+            1500.0,
+            350.0,
+            200.0,
+            default_ordinal,
+        )
+    }
+
+    #[test]
+    fn expected_score() {
+        assert_eq!(Elo::expected(1500.0, 1500.0), 0.5);
+        assert!(relative_eq!(
+            Elo::expected(1900.0, 1500.0),
+            10.0 / 11.0,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[test]
+    fn even_game_moves_half_of_k() {
+        let got = env()
+            .rate(&GameResult::new(
+                vec![
+                    vec![Rating::new(1500.0, 350.0)],
+                    vec![Rating::new(1500.0, 350.0)],
+                ],
+                vec![1, 2],
+            ))
+            .unwrap();
+        assert_eq!(got[0][0].mu, 1516.0);
+        assert_eq!(got[1][0].mu, 1484.0);
+        assert_eq!(got[0][0].sigma, 350.0);
+    }
+
+    #[test]
+    fn wikipedia_tournament_example() {
+        // Wikipedia "Elo rating system": a 1613 player loses to 1609 and 1720, draws with 1477
+        // and beats 1388 and 1586, expecting 2.867 points and ending near 1601
+        let teams = [1613.0, 1609.0, 1720.0, 1477.0, 1388.0, 1586.0]
+            .iter()
+            .map(|mu| vec![Rating::new(*mu, 1.0)])
+            .collect();
+        let got = env()
+            .rate(&GameResult::new(teams, vec![2, 1, 1, 2, 3, 3]))
+            .unwrap();
+        let expected = [1609.0, 1720.0, 1477.0, 1388.0, 1586.0]
+            .iter()
+            .map(|it| Elo::expected(1613.0, *it))
+            .sum::<f64>();
+        assert!(relative_eq!(expected, 2.867, epsilon = 5e-4));
+        assert!(
+            relative_eq!(got[0][0].mu, 1601.0, epsilon = 0.5),
+            "{}",
+            got[0][0]
+        );
+    }
+
+    #[test]
+    fn teammates_split_by_sigma() {
+        let got = env()
+            .rate(&GameResult::new(
+                vec![
+                    vec![Rating::new(750.0, 1.0), Rating::new(750.0, 1.0)],
+                    vec![Rating::new(1500.0, 1.0)],
+                ],
+                vec![1, 2],
+            ))
+            .unwrap();
+        assert_eq!(got[0][0].mu, 758.0);
+        assert_eq!(got[0][1].mu, 758.0);
+        assert_eq!(got[1][0].mu, 1484.0);
+    }
+
+    #[test]
+    fn env_builder_builds_the_model() {
+        let built = EnvBuilder::default()
+            .model(ModelKind::Elo)
+            .mu(1500.0)
+            .sigma(350.0)
+            .build();
+        let reference = env();
+        let game = GameResult::new(
+            vec![
+                vec![Rating::new(1550.0, 80.0)],
+                vec![Rating::new(1450.0, 120.0)],
+            ],
+            vec![1, 2],
+        );
+        let got = built.rate(&game).unwrap();
+        let want = reference.rate(&game).unwrap();
+        for (g, w) in got.iter().flatten().zip(want.iter().flatten()) {
+            assert_eq!((g.mu, g.sigma), (w.mu, w.sigma));
+        }
+    }
+}
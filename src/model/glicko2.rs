@@ -0,0 +1,309 @@
+use crate::{
+    constant::KAPPA,
+    explain::{OpponentContribution, TeamExplanation},
+    import::GLICKO2_SCALE,
+    math::score,
+    rating::{sum_aggregate, AggregateFunc, GameResult, Rating},
+};
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use super::model::Model;
+
+/// Convergence tolerance of the volatility iteration.
+const TOLERANCE: f64 = 0.000001;
+
+/// Glicko-2 (Glickman, "Example of the Glicko-2 system"), for comparison with the Weng-Lin
+/// models. `mu` is the Glicko rating and `sigma` the rating deviation, e.g. 1500 and 350.
+///
+/// `Rating` has nowhere to keep a volatility, so through `Model` every game is a rating period
+/// that starts from `volatility`; `rate_period` takes and returns the real one.
+pub struct Glicko2 {
+    aggregate: AggregateFunc,
+    tau: f64,
+    volatility: f64,
+}
+
+struct Period {
+    rating: Rating,
+    volatility: f64,
+    /// Rating change and share of the information gained per opponent.
+    omegas: Vec<f64>,
+    information: Vec<f64>,
+}
+
+impl Glicko2 {
+    pub fn new(tau: f64, volatility: f64) -> Self {
+        Self {
+            aggregate: sum_aggregate,
+            tau,
+            volatility,
+        }
+    }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// One rating period of `rating` with `volatility` against `games` of (opponent, score),
+    /// score being 1.0 for a win, 0.5 for a draw and 0.0 for a loss. Returns the new rating
+    /// and volatility.
+    pub fn rate_period(
+        &self,
+        rating: &Rating,
+        volatility: f64,
+        games: &[(Rating, f64)],
+    ) -> (Rating, f64) {
+        let period = self.period(rating, volatility, games);
+        (period.rating, period.volatility)
+    }
+
+    fn period(&self, rating: &Rating, volatility: f64, games: &[(Rating, f64)]) -> Period {
+        let mu = (rating.mu - 1500.0) / GLICKO2_SCALE;
+        let phi = rating.sigma / GLICKO2_SCALE;
+        if games.is_empty() {
+            return Period {
+                rating: Rating::new(
+                    rating.mu,
+                    GLICKO2_SCALE * (phi.powi(2) + volatility.powi(2)).sqrt(),
+                ),
+                volatility,
+                omegas: Vec::new(),
+                information: Vec::new(),
+            };
+        }
+
+        let terms: Vec<(f64, f64, f64)> = games
+            .iter()
+            .map(|(opponent, score)| {
+                let g = g(opponent.sigma / GLICKO2_SCALE);
+                let expected =
+                    1.0 / (1.0 + (-g * (mu - (opponent.mu - 1500.0) / GLICKO2_SCALE)).exp());
+                (g, expected, *score)
+            })
+            .collect();
+        let information: Vec<f64> = terms
+            .iter()
+            .map(|(g, expected, _)| g.powi(2) * expected * (1.0 - expected))
+            .collect();
+        let v = 1.0 / information.iter().sum::<f64>();
+        let improvement = terms
+            .iter()
+            .map(|(g, expected, score)| g * (score - expected))
+            .sum::<f64>();
+        let delta = v * improvement;
+
+        let volatility = self.volatility_after(phi, v, delta, volatility);
+        let phi_star_sq = phi.powi(2) + volatility.powi(2);
+        let new_phi = 1.0 / (1.0 / phi_star_sq + 1.0 / v).sqrt();
+        let omegas = terms
+            .iter()
+            .map(|(g, expected, score)| GLICKO2_SCALE * new_phi.powi(2) * g * (score - expected))
+            .collect();
+
+        Period {
+            rating: Rating::new(
+                rating.mu + GLICKO2_SCALE * new_phi.powi(2) * improvement,
+                GLICKO2_SCALE * new_phi,
+            ),
+            volatility,
+            omegas,
+            information,
+        }
+    }
+
+    /// Step 5 of the paper, the Illinois variant of regula falsi.
+    fn volatility_after(&self, phi: f64, v: f64, delta: f64, volatility: f64) -> f64 {
+        let a = volatility.powi(2).ln();
+        let tau_sq = self.tau.powi(2);
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta.powi(2) - phi.powi(2) - v - ex) / (2.0 * (phi.powi(2) + v + ex).powi(2))
+                - (x - a) / tau_sq
+        };
+
+        let mut lower = a;
+        let mut upper = if delta.powi(2) > phi.powi(2) + v {
+            (delta.powi(2) - phi.powi(2) - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * self.tau) < 0.0 {
+                k += 1.0;
+            }
+            a - k * self.tau
+        };
+        let mut f_lower = f(lower);
+        let mut f_upper = f(upper);
+        while (upper - lower).abs() > TOLERANCE {
+            let c = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+            let f_c = f(c);
+            if f_c * f_upper <= 0.0 {
+                lower = upper;
+                f_lower = f_upper;
+            } else {
+                f_lower /= 2.0;
+            }
+            upper = c;
+            f_upper = f_c;
+        }
+        (lower / 2.0).exp()
+    }
+}
+
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Self::new(0.5, 0.06)
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi.powi(2) / PI.powi(2)).sqrt()
+}
+
+impl Model for Glicko2 {
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let team_ratings = result.team_ratings(self.aggregate);
+
+        team_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, team_i)| {
+                let opponents: Vec<usize> = (0..team_ratings.len()).filter(|q| *q != i).collect();
+                let games: Vec<(Rating, f64)> = opponents
+                    .iter()
+                    .map(|q| {
+                        let team_q = &team_ratings[*q];
+                        (
+                            Rating::new(team_q.mu, team_q.sigma_sq.sqrt()),
+                            score(team_q.rank, team_i.rank),
+                        )
+                    })
+                    .collect();
+                let team = Rating::new(team_i.mu, team_i.sigma_sq.sqrt());
+                let period = self.period(&team, self.volatility, &games);
+
+                // sigma² of the team goes to sigma'² = sigma² * (1 - delta), split between
+                // opponents by the information each game carried
+                let delta = 1.0 - (period.rating.sigma / team.sigma).powi(2);
+                let information = period.information.iter().sum::<f64>();
+                let contributions = opponents
+                    .iter()
+                    .enumerate()
+                    .map(|(j, q)| OpponentContribution {
+                        opponent: *q,
+                        omega: result.weight * period.omegas[j],
+                        delta: result.weight * delta * period.information[j] / information,
+                        gamma: 1.0,
+                    })
+                    .collect();
+
+                TeamExplanation::new(team_i, contributions, KAPPA)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        env::Env, env_builder::EnvBuilder, model::kind::ModelKind, rating::default_ordinal,
+    };
+    use alloc::{boxed::Box, vec};
+    use approx::relative_eq;
+
+    #[test]
+    fn glickman_example() {
+        // "Example of the Glicko-2 system", Glickman: tau 0.5, one period of three games
+        let games = [
+            (Rating::new(1400.0, 30.0), 1.0),
+            (Rating::new(1550.0, 100.0), 0.0),
+            (Rating::new(1700.0, 300.0), 0.0),
+        ];
+        let (rating, volatility) =
+            Glicko2::default().rate_period(&Rating::new(1500.0, 200.0), 0.06, &games);
+        assert!(relative_eq!(rating.mu, 1464.06, epsilon = 0.01), "{rating}");
+        assert!(
+            relative_eq!(rating.sigma, 151.52, epsilon = 0.01),
+            "{rating}"
+        );
+        assert!(relative_eq!(volatility, 0.05999, epsilon = 1e-5));
+    }
+
+    #[test]
+    fn glickman_example_through_env() {
+        let env = Env::new(
+            Box::new(Glicko2::default()),
+            3.0,
+            1500.0,
+            350.0,
+            200.0,
+            default_ordinal,
+        );
+        let teams = [
+            (1500.0, 200.0),
+            (1400.0, 30.0),
+            (1550.0, 100.0),
+            (1700.0, 300.0),
+        ]
+        .iter()
+        .map(|(mu, sigma)| vec![Rating::new(*mu, *sigma)])
+        .collect();
+        let got = env.rate(&GameResult::new(teams, vec![2, 3, 1, 1])).unwrap();
+        assert!(
+            relative_eq!(got[0][0].mu, 1464.06, epsilon = 0.01),
+            "{}",
+            got[0][0]
+        );
+        assert!(
+            relative_eq!(got[0][0].sigma, 151.52, epsilon = 0.01),
+            "{}",
+            got[0][0]
+        );
+    }
+
+    #[test]
+    fn idle_period_only_grows_deviation() {
+        let (rating, volatility) =
+            Glicko2::default().rate_period(&Rating::new(1500.0, 50.0), 0.06, &[]);
+        assert_eq!(rating.mu, 1500.0);
+        assert!(relative_eq!(
+            rating.sigma,
+            GLICKO2_SCALE * ((50.0 / GLICKO2_SCALE).powi(2) + 0.06f64.powi(2)).sqrt()
+        ));
+        assert_eq!(volatility, 0.06);
+    }
+
+    #[test]
+    fn env_builder_builds_the_model() {
+        let built = EnvBuilder::default()
+            .model(ModelKind::Glicko2)
+            .mu(1500.0)
+            .sigma(350.0)
+            .build();
+        let reference = Env::new(
+            Box::new(Glicko2::default()),
+            3.0,
+            1500.0,
+            350.0,
+            175.0,
+            default_ordinal,
+        );
+        let game = GameResult::new(
+            vec![
+                vec![Rating::new(1550.0, 80.0)],
+                vec![Rating::new(1450.0, 120.0)],
+            ],
+            vec![1, 2],
+        );
+        let got = built.rate(&game).unwrap();
+        let want = reference.rate(&game).unwrap();
+        for (g, w) in got.iter().flatten().zip(want.iter().flatten()) {
+            assert_eq!((g.mu, g.sigma), (w.mu, w.sigma));
+        }
+    }
+}
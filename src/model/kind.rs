@@ -1,4 +1,11 @@
-#[derive(Default, Clone)]
+use crate::error::OpenSkillError;
+use core::str::FromStr;
+
+/// More models can be added, some behind features such as `baselines`, so matches need a
+/// wildcard arm.
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum ModelKind {
     #[default]
     PlackettLuce,
@@ -6,4 +13,35 @@ pub enum ModelKind {
     BradleyTerryFull,
     ThurstoneMostellerPart,
     ThurstoneMostellerFull,
+    /// `Elo::default()`; set `mu` to the Elo scale, e.g. 1500.
+    #[cfg(feature = "baselines")]
+    Elo,
+    /// `Glicko2::default()`; set `mu` and `sigma` to the Glicko scale, e.g. 1500 and 350.
+    #[cfg(feature = "baselines")]
+    Glicko2,
+    /// `TrueSkill` with the env's `beta` and `tau = sigma / 100`.
+    #[cfg(feature = "baselines")]
+    TrueSkill,
+}
+
+impl FromStr for ModelKind {
+    type Err = OpenSkillError;
+
+    /// Parses the model class names used by openskill.py, e.g. `"PlackettLuce"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PlackettLuce" => Ok(Self::PlackettLuce),
+            "BradleyTerryPart" => Ok(Self::BradleyTerryPart),
+            "BradleyTerryFull" => Ok(Self::BradleyTerryFull),
+            "ThurstoneMostellerPart" => Ok(Self::ThurstoneMostellerPart),
+            "ThurstoneMostellerFull" => Ok(Self::ThurstoneMostellerFull),
+            #[cfg(feature = "baselines")]
+            "Elo" => Ok(Self::Elo),
+            #[cfg(feature = "baselines")]
+            "Glicko2" => Ok(Self::Glicko2),
+            #[cfg(feature = "baselines")]
+            "TrueSkill" => Ok(Self::TrueSkill),
+            _ => Err(OpenSkillError::InvalidArgument("unknown model")),
+        }
+    }
 }
@@ -1,8 +1,15 @@
 pub mod bradley_terry_full;
 pub mod bradley_terry_part;
+#[cfg(feature = "baselines")]
+pub mod elo;
+#[cfg(feature = "baselines")]
+pub mod glicko2;
 pub mod kind;
+#[allow(clippy::module_inception)]
 pub mod model;
 pub mod plackett_luce;
 mod test_util;
 pub mod thurstone_mosteller_full;
 pub mod thurstone_mosteller_part;
+#[cfg(feature = "baselines")]
+pub mod trueskill;
@@ -1,5 +1,32 @@
-use crate::rating::Rating;
+use crate::{
+    explain::TeamExplanation,
+    rating::{GameResult, Rating},
+};
+use alloc::vec::Vec;
 
-pub trait Model {
-    fn rate(&self, teams: Vec<Vec<Rating>>, ranks: Vec<usize>) -> Vec<Vec<Rating>>;
+/// A rating model. Models are `Send + Sync` so that an `Env` can be shared between threads,
+/// as `Env::rate_batch` with `rayon` and the Python bindings releasing the GIL do; a model
+/// with interior mutability has to use a `Mutex` or atomics.
+pub trait Model: Send + Sync {
+    fn rate(&self, result: &GameResult) -> Vec<Vec<Rating>> {
+        self.rate_explained(result)
+            .into_iter()
+            .map(|it| it.ratings)
+            .collect()
+    }
+
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation>;
+
+    /// Rates the same teams once per entry of `outcomes`, each a full set of ranks.
+    /// Models override this to share rank independent work between outcomes.
+    fn rate_outcomes(&self, result: &GameResult, outcomes: &[Vec<usize>]) -> Vec<Vec<Vec<Rating>>> {
+        outcomes
+            .iter()
+            .map(|ranks| {
+                let mut result = result.clone();
+                result.ranks = ranks.clone();
+                self.rate(&result)
+            })
+            .collect()
+    }
 }
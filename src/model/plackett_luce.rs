@@ -1,8 +1,14 @@
+use crate::explain::{OpponentContribution, TeamExplanation};
 use crate::model::model::Model;
-use crate::rating::{to_team_ratings, GammaFunc, Rating, TeamRating};
-use crate::utils::update_team_rating;
+use crate::rating::{sum_aggregate, AggregateFunc, GameResult, GammaFunc, Rating, TeamRating};
+use alloc::{vec, vec::Vec};
+use core::iter::zip;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 pub struct PlackettLuce {
+    aggregate: AggregateFunc,
     beta_sq: f64,
     gamma: GammaFunc,
     kappa: f64,
@@ -11,49 +17,85 @@ pub struct PlackettLuce {
 impl PlackettLuce {
     pub fn new(beta: f64, kappa: f64, gamma: GammaFunc) -> Self {
         Self {
+            aggregate: sum_aggregate,
             beta_sq: beta.powi(2),
             gamma,
             kappa,
         }
     }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
 }
 
-impl Model for PlackettLuce {
-    fn rate(&self, teams: Vec<Vec<Rating>>, ranks: Vec<usize>) -> Vec<Vec<Rating>> {
-        let team_ratings = to_team_ratings(&teams, Some(ranks));
-        let c = calc_c(&team_ratings, self.beta_sq);
-        let sum_q = calc_sum_q(&team_ratings, c);
-        let a = calc_a(&team_ratings);
+impl PlackettLuce {
+    fn explain(&self, team_ratings: &[TeamRating], c: f64, weight: f64) -> Vec<TeamExplanation> {
+        let sum_q = calc_sum_q(team_ratings, c);
+        let a = calc_a(team_ratings);
 
         let num_teams = team_ratings.len() as f64;
         team_ratings
             .iter()
             .enumerate()
             .map(|(i, team_i)| {
-                let (mut omega, mut delta) = (0.0, 0.0);
                 let mu_over_c = (team_i.mu / c).exp();
-                for (q, team_q) in team_ratings.iter().enumerate() {
-                    let mu_c_over_sum_q = mu_over_c / sum_q[q];
-                    if team_q.rank <= team_i.rank {
-                        delta += (mu_c_over_sum_q * (1.0 - mu_c_over_sum_q)) / a[q];
-                        if q == i {
-                            omega += (1.0 - mu_c_over_sum_q) / a[q]
+                let gamma = (self.gamma)(c, num_teams, team_i);
+                let omega_scale = weight * team_i.sigma_sq / c;
+                let delta_scale = weight * gamma * team_i.sigma_sq / c.powi(2);
+                let contributions = team_ratings
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, team_q)| team_q.rank <= team_i.rank)
+                    .map(|(q, _)| {
+                        let mu_c_over_sum_q = mu_over_c / sum_q[q];
+                        let omega = if q == i {
+                            (1.0 - mu_c_over_sum_q) / a[q]
                         } else {
-                            omega -= mu_c_over_sum_q / a[q]
+                            -mu_c_over_sum_q / a[q]
+                        };
+                        let delta = (mu_c_over_sum_q * (1.0 - mu_c_over_sum_q)) / a[q];
+                        OpponentContribution {
+                            opponent: q,
+                            omega: omega * omega_scale,
+                            delta: delta * delta_scale,
+                            gamma,
                         }
-                    }
-                }
-                omega *= team_i.sigma_sq / c;
-                delta *= team_i.sigma_sq / c.powi(2);
-                delta *= (self.gamma)(c, num_teams, team_i);
+                    })
+                    .collect();
 
-                update_team_rating(team_i, omega, delta, self.kappa)
+                TeamExplanation::new(team_i, contributions, self.kappa)
+            })
+            .collect()
+    }
+}
+
+impl Model for PlackettLuce {
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let team_ratings = result.team_ratings(self.aggregate);
+        let c = calc_c(&team_ratings, self.beta_sq);
+        self.explain(&team_ratings, c, result.weight)
+    }
+
+    fn rate_outcomes(&self, result: &GameResult, outcomes: &[Vec<usize>]) -> Vec<Vec<Vec<Rating>>> {
+        let mut team_ratings = result.team_ratings(self.aggregate);
+        // c only depends on sigma, so it is shared by every outcome
+        let c = calc_c(&team_ratings, self.beta_sq);
+        outcomes
+            .iter()
+            .map(|ranks| {
+                zip(team_ratings.iter_mut(), ranks).for_each(|(team, rank)| team.rank = *rank);
+                self.explain(&team_ratings, c, result.weight)
+                    .into_iter()
+                    .map(|it| it.ratings)
+                    .collect()
             })
             .collect()
     }
 }
 
-fn calc_c(teams: &Vec<TeamRating>, beta_squared: f64) -> f64 {
+fn calc_c(teams: &[TeamRating], beta_squared: f64) -> f64 {
     teams
         .iter()
         .map(|team| team.sigma_sq + beta_squared)
@@ -61,7 +103,7 @@ fn calc_c(teams: &Vec<TeamRating>, beta_squared: f64) -> f64 {
         .sqrt()
 }
 
-fn calc_sum_q(teams: &Vec<TeamRating>, c: f64) -> Vec<f64> {
+fn calc_sum_q(teams: &[TeamRating], c: f64) -> Vec<f64> {
     let mut res = vec![0.0; teams.len()];
     for team_i in teams.iter() {
         let summed = (team_i.mu / c).exp();
@@ -76,7 +118,7 @@ fn calc_sum_q(teams: &Vec<TeamRating>, c: f64) -> Vec<f64> {
     res
 }
 
-fn calc_a(teams: &Vec<TeamRating>) -> Vec<f64> {
+fn calc_a(teams: &[TeamRating]) -> Vec<f64> {
     teams
         .iter()
         .map(|team| {
@@ -2,15 +2,19 @@
 mod test {
     use approx::relative_eq;
 
-    use crate::{model::model::Model, rating::Rating};
+    use crate::{
+        model::model::Model,
+        rating::{GameResult, Rating},
+    };
 
+    #[allow(dead_code)]
     fn model_test_func(
         teams: Vec<Vec<Rating>>,
         ranks: Vec<usize>,
         want: Vec<Vec<Rating>>,
         model: Box<dyn Model>,
     ) {
-        let got = model.rate(teams, ranks);
+        let got = model.rate(&GameResult::new(teams, ranks));
         assert_eq!(got.len(), want.len());
         got.iter().zip(want.iter()).for_each(|(g, w)| {
             assert_eq!(g.len(), w.len());
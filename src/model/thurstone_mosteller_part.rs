@@ -1,10 +1,14 @@
+use crate::explain::{OpponentContribution, TeamExplanation};
 use crate::math::{v, vt, w, wt};
 use crate::model::model::Model;
-use crate::rating::{ladder_pairs, to_team_ratings, GammaFunc, Rating};
-use crate::utils::update_team_rating;
-use std::iter::zip;
+use crate::rating::{ladder_pairs, sum_aggregate, AggregateFunc, GameResult, GammaFunc};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 pub struct ThurstoneMostellerPart {
+    aggregate: AggregateFunc,
     kappa: f64,
     gamma: GammaFunc,
     two_beta_sq: f64,
@@ -13,43 +17,63 @@ pub struct ThurstoneMostellerPart {
 impl ThurstoneMostellerPart {
     pub fn new(beta: f64, kappa: f64, gamma: GammaFunc) -> Self {
         Self {
+            aggregate: sum_aggregate,
             kappa,
             gamma,
             two_beta_sq: 2.0 * beta.powi(2),
         }
     }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
 }
 
 impl Model for ThurstoneMostellerPart {
-    fn rate(&self, teams: Vec<Vec<Rating>>, ranks: Vec<usize>) -> Vec<Vec<Rating>> {
-        let team_ratings = to_team_ratings(&teams, Some(ranks));
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let team_ratings = result.team_ratings(self.aggregate);
         let num_teams = team_ratings.len() as f64;
-        let adjacent_teams = ladder_pairs(&team_ratings);
-        zip(&team_ratings, adjacent_teams)
-            .map(|(team_i, adj_i)| {
-                let (omega, delta) = adj_i.iter().fold((0., 0.), |(omega, delta), team_q| {
-                    let ciq = 2. * (team_i.sigma_sq + team_q.sigma_sq + self.two_beta_sq).sqrt();
-                    let delta_mu = (team_i.mu - team_q.mu) / ciq;
-                    let sigma_sq_to_ciq = team_i.sigma_sq / ciq;
-                    let i_gamma = (self.gamma)(ciq, num_teams, &team_i);
-                    if team_q.rank == team_i.rank {
-                        (
-                            omega + sigma_sq_to_ciq * vt(delta_mu, self.kappa / ciq),
-                            delta
-                                + ((i_gamma * sigma_sq_to_ciq) / ciq)
+        let adjacent = ladder_pairs(&(0..team_ratings.len()).collect::<Vec<_>>());
+
+        team_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, team_i)| {
+                let contributions = adjacent[i]
+                    .iter()
+                    .copied()
+                    .map(|q| {
+                        let team_q = &team_ratings[q];
+                        let ciq =
+                            2. * (team_i.sigma_sq + team_q.sigma_sq + self.two_beta_sq).sqrt();
+                        let delta_mu = (team_i.mu - team_q.mu) / ciq;
+                        let sigma_sq_to_ciq = team_i.sigma_sq / ciq;
+                        let i_gamma = (self.gamma)(ciq, num_teams, team_i);
+                        let (omega, delta) = if team_q.rank == team_i.rank {
+                            (
+                                sigma_sq_to_ciq * vt(delta_mu, self.kappa / ciq),
+                                ((i_gamma * sigma_sq_to_ciq) / ciq)
                                     * wt(delta_mu, self.kappa / ciq),
-                        )
-                    } else {
-                        let sign = if team_q.rank > team_i.rank { 1. } else { -1. };
-                        (
-                            omega + sign * sigma_sq_to_ciq * v(sign * delta_mu, self.kappa / ciq),
-                            delta
-                                + ((i_gamma * sigma_sq_to_ciq) / ciq)
+                            )
+                        } else {
+                            let sign = if team_q.rank > team_i.rank { 1. } else { -1. };
+                            (
+                                sign * sigma_sq_to_ciq * v(sign * delta_mu, self.kappa / ciq),
+                                ((i_gamma * sigma_sq_to_ciq) / ciq)
                                     * w(sign * delta_mu, self.kappa / ciq),
-                        )
-                    }
-                });
-                update_team_rating(&team_i, omega, delta, self.kappa)
+                            )
+                        };
+                        OpponentContribution {
+                            opponent: q,
+                            omega: result.weight * omega,
+                            delta: result.weight * delta,
+                            gamma: i_gamma,
+                        }
+                    })
+                    .collect();
+
+                TeamExplanation::new(team_i, contributions, self.kappa)
             })
             .collect()
     }
@@ -0,0 +1,223 @@
+use crate::{
+    constant::KAPPA,
+    explain::{OpponentContribution, TeamExplanation},
+    math::{normal, v, w, wt},
+    rating::{sum_aggregate, AggregateFunc, GameResult},
+};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use super::model::Model;
+
+/// Draw probability of the reference `trueskill` package.
+pub const DRAW_PROBABILITY: f64 = 0.1;
+
+/// Two team TrueSkill (Herbrich et al.), for comparison with the Weng-Lin models; the defaults
+/// match the reference `trueskill` package. Every rating first gets `tau` added as dynamics.
+/// With more than two teams each team sums its two team updates against every other team,
+/// which is an approximation rather than TrueSkill's full factor graph.
+pub struct TrueSkill {
+    aggregate: AggregateFunc,
+    beta: f64,
+    tau: f64,
+    draw_probability: f64,
+}
+
+impl TrueSkill {
+    pub fn new(beta: f64, tau: f64, draw_probability: f64) -> Self {
+        Self {
+            aggregate: sum_aggregate,
+            beta,
+            tau,
+            draw_probability,
+        }
+    }
+
+    pub fn with_aggregate(mut self, aggregate: AggregateFunc) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    fn draw_margin(&self, players: usize) -> f64 {
+        normal().inverse_cdf((self.draw_probability + 1.0) / 2.0)
+            * (players as f64).sqrt()
+            * self.beta
+    }
+}
+
+impl Default for TrueSkill {
+    fn default() -> Self {
+        let sigma = 25.0 / 3.0;
+        Self::new(sigma / 2.0, sigma / 100.0, DRAW_PROBABILITY)
+    }
+}
+
+/// Mean shift of a standard normal truncated to `[-margin - t, margin - t]`, for draws.
+fn v_draw(t: f64, margin: f64) -> f64 {
+    let denom = normal().cdf(margin - t) - normal().cdf(-margin - t);
+    if denom < f64::EPSILON {
+        if t < 0.0 {
+            -t - margin
+        } else {
+            -t + margin
+        }
+    } else {
+        (normal().pdf(-margin - t) - normal().pdf(margin - t)) / denom
+    }
+}
+
+impl Model for TrueSkill {
+    fn rate_explained(&self, result: &GameResult) -> Vec<TeamExplanation> {
+        let mut result = result.clone();
+        let tau_sq = self.tau.powi(2);
+        for (i, team) in result.teams.iter_mut().enumerate() {
+            for (j, it) in team.iter_mut().enumerate() {
+                if !result.anchored.contains(&(i, j)) {
+                    it.sigma = (it.sigma.powi(2) + tau_sq).sqrt();
+                }
+            }
+        }
+        let team_ratings = result.team_ratings(self.aggregate);
+        let beta_sq = self.beta.powi(2);
+
+        team_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, team_i)| {
+                let contributions = (0..team_ratings.len())
+                    .filter(|q| *q != i)
+                    .map(|q| {
+                        let team_q = &team_ratings[q];
+                        let players = team_i.team_size() + team_q.team_size();
+                        let c =
+                            (players as f64 * beta_sq + team_i.sigma_sq + team_q.sigma_sq).sqrt();
+                        let margin = self.draw_margin(players) / c;
+                        let t = (team_i.mu - team_q.mu) / c;
+                        let (v, w) = if team_i.rank < team_q.rank {
+                            (v(t, margin), w(t, margin))
+                        } else if team_i.rank > team_q.rank {
+                            (-v(-t, margin), w(-t, margin))
+                        } else {
+                            (v_draw(t, margin), wt(t, margin))
+                        };
+                        OpponentContribution {
+                            opponent: q,
+                            omega: result.weight * team_i.sigma_sq / c * v,
+                            delta: result.weight * team_i.sigma_sq / c.powi(2) * w,
+                            gamma: 1.0,
+                        }
+                    })
+                    .collect();
+
+                TeamExplanation::new(team_i, contributions, KAPPA)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        env::Env, env_builder::EnvBuilder, model::kind::ModelKind, rating::default_ordinal,
+        rating::Rating,
+    };
+    use alloc::{boxed::Box, vec};
+    use approx::relative_eq;
+
+    fn env() -> Env {
+        let sigma = 25.0 / 3.0;
+        Env::new(
+            Box::new(TrueSkill::default()),
+            3.0,
+            25.0,
+            sigma,
+            sigma / 2.0,
+            default_ordinal,
+        )
+    }
+
+    fn assert_rating(got: &Rating, mu: f64, sigma: f64) {
+        assert!(relative_eq!(got.mu, mu, epsilon = 1e-3), "{got}");
+        assert!(relative_eq!(got.sigma, sigma, epsilon = 1e-3), "{got}");
+    }
+
+    // reference numbers from the documentation of the `trueskill` Python package
+    #[test]
+    fn rate_1vs1() {
+        let got = env()
+            .rate(&GameResult::new(
+                vec![vec![Rating::default()], vec![Rating::default()]],
+                vec![1, 2],
+            ))
+            .unwrap();
+        assert_rating(&got[0][0], 29.396, 7.171);
+        assert_rating(&got[1][0], 20.604, 7.171);
+    }
+
+    #[test]
+    fn draw_1vs1() {
+        let got = env()
+            .rate(&GameResult::new(
+                vec![vec![Rating::default()], vec![Rating::default()]],
+                vec![1, 1],
+            ))
+            .unwrap();
+        assert_rating(&got[0][0], 25.0, 6.458);
+        assert_rating(&got[1][0], 25.0, 6.458);
+    }
+
+    #[test]
+    fn draw_pulls_ratings_together() {
+        let got = env()
+            .rate(&GameResult::new(
+                vec![vec![Rating::new(30.0, 4.0)], vec![Rating::new(20.0, 4.0)]],
+                vec![1, 1],
+            ))
+            .unwrap();
+        assert!(got[0][0].mu < 30.0);
+        assert!(got[1][0].mu > 20.0);
+    }
+
+    #[test]
+    fn teams_match_member_updates() {
+        let teams = vec![
+            vec![Rating::default(), Rating::new(30.0, 3.0)],
+            vec![Rating::new(27.0, 5.0)],
+        ];
+        let got = env()
+            .rate(&GameResult::new(teams.clone(), vec![2, 1]))
+            .unwrap();
+        // TrueSkill moves each member by its own sigma², like the shares do
+        let gain = |team: usize, player: usize| got[team][player].mu - teams[team][player].mu;
+        let sigma_sq = |team: usize, player: usize| {
+            teams[team][player].sigma.powi(2) + (25.0f64 / 300.0).powi(2)
+        };
+        assert!(relative_eq!(
+            gain(0, 0) / gain(0, 1),
+            sigma_sq(0, 0) / sigma_sq(0, 1),
+            epsilon = 1e-9
+        ));
+        assert!(gain(1, 0) > 0.0);
+    }
+
+    #[test]
+    fn env_builder_builds_the_model() {
+        let built = EnvBuilder::default().model(ModelKind::TrueSkill).build();
+        let reference = env();
+        let game = GameResult::new(
+            vec![
+                vec![Rating::new(1550.0, 80.0)],
+                vec![Rating::new(1450.0, 120.0)],
+            ],
+            vec![1, 2],
+        );
+        let got = built.rate(&game).unwrap();
+        let want = reference.rate(&game).unwrap();
+        for (g, w) in got.iter().flatten().zip(want.iter().flatten()) {
+            assert_eq!((g.mu, g.sigma), (w.mu, w.sigma));
+        }
+    }
+}
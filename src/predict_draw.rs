@@ -2,45 +2,86 @@ use crate::math::{self, normal};
 use crate::rating::calc_total_player;
 use crate::{
     error::OpenSkillError,
-    rating::{to_team_ratings, Rating},
+    rating::{sum_aggregate, to_team_ratings, Rating, TeamRating},
     validate::validate_team,
 };
-use itertools::Itertools;
-use statrs::distribution::ContinuousCDF;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 const ERR_MUST_CONTAIN_AT_LEAST_1_PLAYER: OpenSkillError =
     OpenSkillError::InvalidTeamCount("team must contain atleast 1 player");
 
-pub fn predict_draw(teams: &Vec<Vec<Rating>>, beta: f64) -> Result<f64, OpenSkillError> {
+pub fn predict_draw(teams: &[Vec<Rating>], beta: f64) -> Result<f64, OpenSkillError> {
+    validate_team(teams)?;
+    predict_draw_teams(&to_team_ratings(teams, None, sum_aggregate), beta)
+}
+
+pub(crate) fn predict_draw_teams(
+    team_ratings: &[TeamRating],
+    beta: f64,
+) -> Result<f64, OpenSkillError> {
+    let matrix = draw_matrix_teams(team_ratings, beta)?;
+
+    let num_teams = team_ratings.len();
+    if num_teams == 1 {
+        return Ok(1.0);
+    }
+    let denom = math::denominator(num_teams, if num_teams > 2 { 1 } else { 2 });
+
+    Ok(matrix
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().filter(move |(q, _)| *q != i))
+        .map(|(_, p)| p)
+        .sum::<f64>()
+        .abs()
+        / denom)
+}
+
+/// `matrix[i][j]` is the pairwise draw term of teams `i` and `j` using the draw margin of the
+/// whole game; the diagonal is 1.0.
+pub fn draw_matrix(teams: &[Vec<Rating>], beta: f64) -> Result<Vec<Vec<f64>>, OpenSkillError> {
+    validate_team(teams)?;
+    draw_matrix_teams(&to_team_ratings(teams, None, sum_aggregate), beta)
+}
+
+pub(crate) fn draw_matrix_teams(
+    team_ratings: &[TeamRating],
+    beta: f64,
+) -> Result<Vec<Vec<f64>>, OpenSkillError> {
     let beta_squared = beta.powi(2);
-    validate_team(&teams)?;
 
-    let num_teams = teams.len();
-    match num_teams {
-        0 => return Err(ERR_MUST_CONTAIN_AT_LEAST_1_PLAYER),
-        1 => return Ok(1.0),
-        _ => {}
+    let num_teams = team_ratings.len();
+    if num_teams == 0 {
+        return Err(ERR_MUST_CONTAIN_AT_LEAST_1_PLAYER);
     }
 
-    let team_ratings = to_team_ratings(teams, None);
-    let denom = math::denominator(num_teams, if num_teams > 2 { 1 } else { 2 });
-    let total_player = calc_total_player(&team_ratings);
+    let total_player = calc_total_player(team_ratings);
     let draw_margin = math::draw_margin(num_teams, total_player, beta);
 
     Ok(team_ratings
-        .into_iter()
-        .permutations(2)
-        .map(|it| {
-            let (mu_a, sigma_a) = (it[0].mu, it[0].sigma_sq);
-            let (mu_b, sigma_b) = (it[1].mu, it[1].sigma_sq);
-            let total_player = calc_total_player(&it);
-            let sigma_bar = math::sigma_bar(sigma_a, sigma_b, beta_squared, total_player);
-            normal().cdf((draw_margin - mu_a + mu_b) / sigma_bar)
-                - normal().cdf((mu_a - mu_b - draw_margin) / sigma_bar)
+        .iter()
+        .enumerate()
+        .map(|(i, team_a)| {
+            team_ratings
+                .iter()
+                .enumerate()
+                .map(|(q, team_b)| {
+                    if i == q {
+                        return 1.0;
+                    }
+                    let (mu_a, sigma_a) = (team_a.mu, team_a.sigma_sq);
+                    let (mu_b, sigma_b) = (team_b.mu, team_b.sigma_sq);
+                    let total_player = team_a.team_size() + team_b.team_size();
+                    let sigma_bar = math::sigma_bar(sigma_a, sigma_b, beta_squared, total_player);
+                    normal().cdf((draw_margin - mu_a + mu_b) / sigma_bar)
+                        - normal().cdf((mu_a - mu_b - draw_margin) / sigma_bar)
+                })
+                .collect()
         })
-        .sum::<f64>()
-        .abs()
-        / denom)
+        .collect())
 }
 
 #[cfg(test)]
@@ -96,6 +137,23 @@ mod tests {
         predict_draw_test(teams, want);
     }
 
+    #[test]
+    fn draw_matrix_sums_to_predict_draw() {
+        let teams = vec![
+            vec![Rating::default(), Rating::new(32.444, 1.123)],
+            vec![Rating::new(35.881, 0.0001), Rating::new(25.188, 1.421)],
+            vec![Rating::default()],
+        ];
+        let matrix = draw_matrix(&teams, DEFAULT_BETA).unwrap();
+        let off_diagonal = (0..3)
+            .flat_map(|i| (0..3).filter(move |j| *j != i).map(move |j| (i, j)))
+            .map(|(i, j)| matrix[i][j])
+            .sum::<f64>();
+        let got = predict_draw(&teams, DEFAULT_BETA).unwrap();
+        assert!(relative_eq!(got, off_diagonal.abs() / 6.0, epsilon = 1e-12));
+        assert_eq!(matrix[1][1], 1.0);
+    }
+
     fn predict_draw_test(teams: Vec<Vec<Rating>>, want: Result<f64, OpenSkillError>) {
         let beta = DEFAULT_BETA;
         let got = predict_draw(&teams, beta);
@@ -109,7 +167,7 @@ mod tests {
             (Err(got), Err(want)) => {
                 assert_eq!(got, want, "got: {got}, want: {want}");
             }
-            _ => assert!(false, "got: {got:?}, want: {want:?}"),
+            _ => panic!("got: {got:?}, want: {want:?}"),
         };
     }
 }
@@ -0,0 +1,100 @@
+use crate::{
+    error::OpenSkillError,
+    math::{self, normal},
+    rating::{calc_total_player, sum_aggregate, to_team_ratings, Rating, TeamRating},
+    validate::validate_team,
+};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+const ERR_MUST_CONTAIN_2_TEAMS: OpenSkillError =
+    OpenSkillError::InvalidTeamCount("outcome prediction requires exactly 2 teams");
+
+/// Win, draw and loss probabilities of the first team; they always sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutcomeProbability {
+    pub win: f64,
+    pub draw: f64,
+    pub loss: f64,
+}
+
+pub fn predict_outcome(
+    teams: &[Vec<Rating>],
+    beta: f64,
+) -> Result<OutcomeProbability, OpenSkillError> {
+    validate_team(teams)?;
+    predict_outcome_teams(&to_team_ratings(teams, None, sum_aggregate), beta)
+}
+
+pub(crate) fn predict_outcome_teams(
+    team_ratings: &[TeamRating],
+    beta: f64,
+) -> Result<OutcomeProbability, OpenSkillError> {
+    if team_ratings.len() != 2 {
+        return Err(ERR_MUST_CONTAIN_2_TEAMS);
+    }
+
+    let total_player = calc_total_player(team_ratings);
+    let draw_margin = math::draw_margin(2, total_player, beta);
+    let (team_a, team_b) = (&team_ratings[0], &team_ratings[1]);
+    let sigma_bar = math::sigma_bar(team_a.sigma_sq, team_b.sigma_sq, beta.powi(2), total_player);
+
+    let diff = team_a.mu - team_b.mu;
+    let win = normal().cdf((diff - draw_margin) / sigma_bar);
+    let loss = normal().cdf((-diff - draw_margin) / sigma_bar);
+    Ok(OutcomeProbability {
+        win,
+        draw: 1.0 - win - loss,
+        loss,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constant::DEFAULT_BETA;
+    use approx::relative_eq;
+
+    #[test]
+    fn requires_two_teams() {
+        let teams = vec![vec![Rating::default()]; 3];
+        assert_eq!(
+            predict_outcome(&teams, DEFAULT_BETA).unwrap_err(),
+            ERR_MUST_CONTAIN_2_TEAMS
+        );
+    }
+
+    #[test]
+    fn equal_teams_are_symmetric() {
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+        let got = predict_outcome(&teams, DEFAULT_BETA).unwrap();
+        assert!(relative_eq!(got.win, got.loss, epsilon = 1e-12));
+        assert!(relative_eq!(
+            got.win + got.draw + got.loss,
+            1.0,
+            epsilon = 1e-12
+        ));
+        assert!(got.draw > 0.0);
+    }
+
+    #[test]
+    fn stronger_team_is_favoured() {
+        let teams = vec![
+            vec![Rating::new(35.0, 3.0), Rating::new(30.0, 4.0)],
+            vec![Rating::default(), Rating::new(24.0, 5.0)],
+        ];
+        let got = predict_outcome(&teams, DEFAULT_BETA).unwrap();
+        assert!(got.win > got.loss);
+        assert!(relative_eq!(
+            got.win + got.draw + got.loss,
+            1.0,
+            epsilon = 1e-12
+        ));
+
+        let swapped = predict_outcome(&[teams[1].clone(), teams[0].clone()], DEFAULT_BETA).unwrap();
+        assert!(relative_eq!(got.win, swapped.loss, epsilon = 1e-12));
+        assert!(relative_eq!(got.draw, swapped.draw, epsilon = 1e-12));
+    }
+}
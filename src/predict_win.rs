@@ -1,29 +1,60 @@
 use crate::{
     error::OpenSkillError,
     math::{self, normal},
-    rating::{to_team_ratings, Rating},
-    utils::zip_without_self,
+    rating::{sum_aggregate, to_team_ratings, Rating, TeamRating},
     validate::validate_team,
 };
+use alloc::vec::Vec;
 
-use statrs::distribution::ContinuousCDF;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
-pub fn predict_win(teams: &Vec<Vec<Rating>>, beta: f64) -> Result<Vec<f64>, OpenSkillError> {
-    let beta_squared = beta.powi(2);
+pub fn predict_win(teams: &[Vec<Rating>], beta: f64) -> Result<Vec<f64>, OpenSkillError> {
+    validate_team(teams)?;
+    Ok(predict_win_teams(
+        &to_team_ratings(teams, None, sum_aggregate),
+        beta,
+    ))
+}
 
-    validate_team(&teams)?;
+pub(crate) fn predict_win_teams(team_ratings: &[TeamRating], beta: f64) -> Vec<f64> {
+    let matrix = win_matrix_teams(team_ratings, beta);
+    let denom = math::denominator(team_ratings.len(), 2);
 
-    let num_teams = teams.len();
-    let team_ratings = to_team_ratings(teams, None);
-    let denom = math::denominator(num_teams, 2);
+    matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(q, _)| *q != i)
+                .map(|(_, p)| p)
+                .sum::<f64>()
+                / denom
+        })
+        .collect()
+}
 
-    Ok(zip_without_self(&team_ratings)
-        .map(|(team_i, others)| {
-            others
+/// `matrix[i][j]` is the probability that team `i` beats team `j`; the diagonal is 0.5.
+pub fn win_matrix(teams: &[Vec<Rating>], beta: f64) -> Result<Vec<Vec<f64>>, OpenSkillError> {
+    validate_team(teams)?;
+    Ok(win_matrix_teams(
+        &to_team_ratings(teams, None, sum_aggregate),
+        beta,
+    ))
+}
+
+pub(crate) fn win_matrix_teams(team_ratings: &[TeamRating], beta: f64) -> Vec<Vec<f64>> {
+    let beta_squared = beta.powi(2);
+
+    team_ratings
+        .iter()
+        .map(|team_i| {
+            team_ratings
                 .iter()
                 .map(|team_q| {
                     normal().cdf(
-                        ((*team_i).mu - team_q.mu)
+                        (team_i.mu - team_q.mu)
                             / math::sigma_bar(
                                 team_i.sigma_sq,
                                 team_q.sigma_sq,
@@ -32,10 +63,9 @@ pub fn predict_win(teams: &Vec<Vec<Rating>>, beta: f64) -> Result<Vec<f64>, Open
                             ),
                     )
                 })
-                .sum::<f64>()
-                / denom
+                .collect()
         })
-        .collect())
+        .collect()
 }
 
 #[cfg(test)]
@@ -43,7 +73,7 @@ mod test {
     use super::*;
     use crate::constant::DEFAULT_BETA;
     use approx::relative_eq;
-    use std::iter::zip;
+    use core::iter::zip;
 
     fn predict_win_test(teams: Vec<Vec<Rating>>, want: Result<Vec<f64>, OpenSkillError>) {
         let got = predict_win(&teams, DEFAULT_BETA);
@@ -60,7 +90,7 @@ mod test {
             (Err(got), Err(want)) => {
                 assert_eq!(got, want);
             }
-            _ => assert!(false, "got: {got:?}, want: {want:?}"),
+            _ => panic!("got: {got:?}, want: {want:?}"),
         };
     }
 
@@ -134,4 +164,26 @@ mod test {
         ]);
         predict_win_test(teams, want);
     }
+
+    #[test]
+    fn win_matrix_is_complementary() {
+        let teams = vec![
+            vec![Rating::default(), Rating::new(32.444, 5.123)],
+            vec![Rating::new(73.381, 1.421), Rating::new(25.188, 6.211)],
+            vec![Rating::new(32.444, 5.123)],
+        ];
+        let matrix = win_matrix(&teams, DEFAULT_BETA).unwrap();
+        assert_eq!(matrix.len(), 3);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 0.5);
+            for (j, p) in row.iter().enumerate() {
+                assert!(relative_eq!(p + matrix[j][i], 1.0, epsilon = 1e-12));
+            }
+        }
+        assert!(relative_eq!(
+            matrix[0][1],
+            0.0020706344961249385,
+            epsilon = 1e-6
+        ));
+    }
 }
@@ -0,0 +1,128 @@
+use crate::rating::{Rating, TeamRating};
+use alloc::{vec, vec::Vec};
+use core::iter::zip;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingChange {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+impl RatingChange {
+    pub fn new(before: &Rating, after: &Rating) -> Self {
+        Self {
+            mu: after.mu - before.mu,
+            sigma: after.sigma - before.sigma,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamPreview {
+    /// `positions[p]` holds the change of every member if the team finishes `p`-th (0 = first).
+    pub positions: Vec<Vec<RatingChange>>,
+    /// Change of every member if all teams draw.
+    pub draw: Vec<RatingChange>,
+}
+
+impl TeamPreview {
+    pub fn win(&self) -> &[RatingChange] {
+        &self.positions[0]
+    }
+
+    pub fn lose(&self) -> &[RatingChange] {
+        &self.positions[self.positions.len() - 1]
+    }
+}
+
+/// Ranks for team `team` finishing at `position`; the other teams keep the order of their
+/// team mu, strongest first, which is the most likely arrangement of the rest of the field.
+pub(crate) fn outcome_ranks(
+    team_ratings: &[TeamRating],
+    team: usize,
+    position: usize,
+) -> Vec<usize> {
+    let mut others: Vec<usize> = (0..team_ratings.len()).filter(|q| *q != team).collect();
+    others.sort_by(|a, b| team_ratings[*b].mu.total_cmp(&team_ratings[*a].mu));
+    others.insert(position, team);
+
+    let mut ranks = vec![0; team_ratings.len()];
+    for (place, q) in others.into_iter().enumerate() {
+        ranks[q] = place + 1;
+    }
+    ranks
+}
+
+pub(crate) fn changes(before: &[Rating], after: &[Rating]) -> Vec<RatingChange> {
+    zip(before, after)
+        .map(|(b, a)| RatingChange::new(b, a))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        env::test::model_kinds,
+        env_builder::EnvBuilder,
+        rating::{GameResult, Rating},
+    };
+    use approx::relative_eq;
+
+    #[test]
+    fn two_team_preview_matches_rate() {
+        let teams = vec![
+            vec![Rating::default(), Rating::new(30.0, 5.0)],
+            vec![Rating::new(27.0, 6.0), Rating::new(22.0, 7.0)],
+        ];
+        for kind in model_kinds() {
+            let env = EnvBuilder::default().model(kind).build();
+            let preview = env.preview(&teams).unwrap();
+            for (ranks, pick) in [(vec![1, 2], 0), (vec![2, 1], 1), (vec![1, 1], 2)] {
+                let rated = env
+                    .rate(&GameResult::new(teams.clone(), ranks.clone()))
+                    .unwrap();
+                for (i, team) in teams.iter().enumerate() {
+                    let got = match (pick, i) {
+                        (2, _) => &preview[i].draw,
+                        (0, 0) | (1, 1) => &preview[i].positions[0],
+                        _ => &preview[i].positions[1],
+                    };
+                    for ((b, a), g) in team.iter().zip(&rated[i]).zip(got) {
+                        assert!(relative_eq!(a.mu - b.mu, g.mu, epsilon = 1e-12));
+                        assert!(relative_eq!(a.sigma - b.sigma, g.sigma, epsilon = 1e-12));
+                    }
+                }
+            }
+            assert!(preview[0].win()[0].mu > 0.0);
+            assert!(preview[0].lose()[0].mu < 0.0);
+        }
+    }
+
+    #[test]
+    fn ffa_preview_improves_with_position() {
+        let teams: Vec<Vec<Rating>> = [24.0, 27.0, 25.0, 21.0]
+            .iter()
+            .map(|mu| vec![Rating::new(*mu, 6.0)])
+            .collect();
+        for kind in model_kinds() {
+            let env = EnvBuilder::default().model(kind.clone()).build();
+            let preview = env.preview(&teams).unwrap();
+            for team in &preview {
+                assert_eq!(team.positions.len(), teams.len());
+                // partial pairing models only compare neighbouring slots, so some places tie
+                for pair in team.positions.windows(2) {
+                    assert!(pair[0][0].mu >= pair[1][0].mu, "{kind:?} {team:?}");
+                }
+                assert!(team.win()[0].mu > team.lose()[0].mu, "{kind:?} {team:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn solo_preview_is_unchanged() {
+        let env = EnvBuilder::default().build();
+        let preview = env.preview(&[vec![Rating::default()]]).unwrap();
+        assert_eq!(preview[0].win()[0].mu, 0.0);
+        assert_eq!(preview[0].draw[0].sigma, 0.0);
+    }
+}
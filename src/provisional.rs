@@ -0,0 +1,334 @@
+use crate::{
+    error::{OpenSkillError, Result},
+    model::model::Model,
+    rating::{GameResult, Rating},
+};
+use alloc::{vec, vec::Vec};
+
+/// Upper bound on the games simulated by `games_until_established`.
+const MAX_SIMULATED_GAMES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RatingStatus {
+    Provisional,
+    Established,
+}
+
+/// When a rating counts as established, and how hard the first games of a player hit.
+/// Without any criteria every rating is established.
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionalPolicy {
+    max_sigma: Option<f64>,
+    min_games: Option<usize>,
+    placement_games: Option<usize>,
+    placement_weight: Option<f64>,
+}
+
+impl ProvisionalPolicy {
+    /// A rating is provisional while its sigma is above `max_sigma`.
+    pub fn max_sigma(mut self, max_sigma: f64) -> Self {
+        self.max_sigma = Some(max_sigma);
+        self
+    }
+
+    /// A rating is provisional until the player has played `min_games`.
+    pub fn min_games(mut self, min_games: usize) -> Self {
+        self.min_games = Some(min_games);
+        self
+    }
+
+    /// The first `games` of a player are placement games, whose updates are scaled by `weight`.
+    pub fn placement(mut self, games: usize, weight: f64) -> Self {
+        self.placement_games = Some(games);
+        self.placement_weight = Some(weight);
+        self
+    }
+
+    pub fn status(&self, rating: &Rating, games: usize) -> RatingStatus {
+        if self.sigma_ok(rating) && self.min_games.is_none_or(|it| games >= it) {
+            RatingStatus::Established
+        } else {
+            RatingStatus::Provisional
+        }
+    }
+
+    fn sigma_ok(&self, rating: &Rating) -> bool {
+        self.max_sigma.is_none_or(|it| rating.sigma <= it)
+    }
+
+    pub fn is_placement(&self, games: usize) -> bool {
+        self.placement_games.is_some_and(|it| games < it)
+    }
+
+    /// Weight multiplier for the next game of a player who has already played `games`.
+    pub fn weight(&self, games: usize) -> f64 {
+        match self.placement_weight {
+            Some(weight) if self.is_placement(games) => weight,
+            _ => 1.0,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(max_sigma) = self.max_sigma {
+            if max_sigma.is_nan() || max_sigma <= 0.0 {
+                return Err(OpenSkillError::InvalidArgument(
+                    "max_sigma must be positive",
+                ));
+            }
+        }
+        if let Some(weight) = self.placement_weight {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(OpenSkillError::InvalidArgument(
+                    "placement weight must be a finite non-negative number",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rates `result` as usual, except that players still in placement (by `games`, laid out like
+/// `result.teams`) take their rating from the same game rated with the placement weight.
+/// Everyone else, and every anchored player, gets exactly the regular update.
+pub(crate) fn rate_placement(
+    model: &dyn Model,
+    result: &GameResult,
+    games: &[Vec<usize>],
+    policy: &ProvisionalPolicy,
+) -> Result<Vec<Vec<Rating>>> {
+    policy.validate()?;
+    if games.len() != result.teams.len()
+        || games
+            .iter()
+            .zip(&result.teams)
+            .any(|(g, t)| g.len() != t.len())
+    {
+        return Err(OpenSkillError::InvalidArgument(
+            "games must have one entry per player",
+        ));
+    }
+
+    let mut rated = model.rate(result);
+    let weight = policy.weight(0);
+    if !games.iter().flatten().any(|it| policy.is_placement(*it)) || weight == 1.0 {
+        return Ok(rated);
+    }
+
+    let boosted = model.rate(&result.clone().weight(result.weight * weight));
+    for (i, team) in games.iter().enumerate() {
+        for (j, played) in team.iter().enumerate() {
+            if policy.is_placement(*played) {
+                rated[i][j] = boosted[i][j].clone();
+            }
+        }
+    }
+    Ok(rated)
+}
+
+/// Expected number of further games until `rating` is established, simulating 1v1 games against
+/// an opponent with the same rating and averaging the sigma left after a win and after a loss.
+/// `None` if that never happens within a reasonable number of games.
+pub(crate) fn games_until_established(
+    model: &dyn Model,
+    rating: &Rating,
+    games: usize,
+    policy: &ProvisionalPolicy,
+) -> Result<Option<usize>> {
+    policy.validate()?;
+    let mut rating = rating.clone();
+    for played in 0..=MAX_SIMULATED_GAMES {
+        if policy.sigma_ok(&rating) {
+            // only the games are missing, and sigma can only shrink from here
+            let remaining = policy
+                .min_games
+                .map_or(0, |it| it.saturating_sub(games + played));
+            return Ok(Some(played + remaining));
+        }
+        let sigma = [vec![1, 2], vec![2, 1]]
+            .into_iter()
+            .map(|ranks| {
+                let game = GameResult::new(vec![vec![rating.clone()], vec![rating.clone()]], ranks)
+                    .weight(policy.weight(games + played));
+                model.rate(&game)[0][0].sigma
+            })
+            .sum::<f64>()
+            / 2.0;
+        if sigma.is_nan() || sigma >= rating.sigma {
+            // sigma no longer shrinks (e.g. a zero placement weight), so it never gets there
+            return Ok(None);
+        }
+        rating.sigma = sigma;
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{env::test::model_kinds, env_builder::EnvBuilder, leaderboard::Leaderboard};
+
+    fn policy() -> ProvisionalPolicy {
+        ProvisionalPolicy::default()
+            .max_sigma(4.0)
+            .min_games(5)
+            .placement(3, 2.0)
+    }
+
+    #[test]
+    fn status_needs_every_criterion() {
+        let policy = policy();
+        assert_eq!(
+            policy.status(&Rating::new(25.0, 3.0), 5),
+            RatingStatus::Established
+        );
+        assert_eq!(
+            policy.status(&Rating::new(25.0, 5.0), 10),
+            RatingStatus::Provisional
+        );
+        assert_eq!(
+            policy.status(&Rating::new(25.0, 3.0), 4),
+            RatingStatus::Provisional
+        );
+        assert_eq!(
+            ProvisionalPolicy::default().status(&Rating::default(), 0),
+            RatingStatus::Established
+        );
+    }
+
+    #[test]
+    fn placement_weight_only_for_first_games() {
+        let policy = policy();
+        assert_eq!(policy.weight(0), 2.0);
+        assert_eq!(policy.weight(2), 2.0);
+        assert_eq!(policy.weight(3), 1.0);
+        assert_eq!(ProvisionalPolicy::default().weight(0), 1.0);
+    }
+
+    #[test]
+    fn placement_players_get_larger_updates() {
+        for kind in model_kinds() {
+            let env = EnvBuilder::default()
+                .model(kind.clone())
+                .provisional(policy())
+                .build();
+            let result = GameResult::new(
+                vec![vec![env.new_rating()], vec![env.new_rating()]],
+                vec![1, 2],
+            );
+            let regular = env.rate(&result).unwrap();
+            let placed = env.rate_placement(&result, &[vec![0], vec![10]]).unwrap();
+
+            assert!(placed[0][0].mu - 25.0 > regular[0][0].mu - 25.0, "{kind:?}");
+            assert!(placed[0][0].sigma < regular[0][0].sigma, "{kind:?}");
+            assert_eq!(placed[1][0].mu, regular[1][0].mu, "{kind:?}");
+            assert_eq!(placed[1][0].sigma, regular[1][0].sigma, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn placement_rejects_mismatched_games() {
+        let env = EnvBuilder::default().provisional(policy()).build();
+        let result = GameResult::new(
+            vec![vec![env.new_rating()], vec![env.new_rating()]],
+            vec![1, 2],
+        );
+        assert_eq!(
+            env.rate_placement(&result, &[vec![0]]).unwrap_err(),
+            OpenSkillError::InvalidArgument("games must have one entry per player")
+        );
+    }
+
+    #[test]
+    fn games_until_established_matches_replayed_games() {
+        let env = EnvBuilder::default()
+            .provisional(ProvisionalPolicy::default().max_sigma(5.0))
+            .build();
+        let rating = env.new_rating();
+        let expected = env.games_until_established(&rating, 0).unwrap().unwrap();
+        assert!(expected > 0);
+
+        // replaying alternating wins and losses against equal opponents lands within a game of the estimate
+        let mut player = rating;
+        let mut played = 0;
+        while env.status(&player, played) == RatingStatus::Provisional {
+            let ranks = if played % 2 == 0 {
+                vec![1, 2]
+            } else {
+                vec![2, 1]
+            };
+            let game = GameResult::new(vec![vec![player.clone()], vec![player.clone()]], ranks);
+            player = env.rate(&game).unwrap()[0][0].clone();
+            played += 1;
+        }
+        assert!(played.abs_diff(expected) <= 1, "{played} {expected}");
+
+        assert_eq!(env.games_until_established(&player, played), Ok(Some(0)));
+    }
+
+    #[test]
+    fn games_until_established_counts_min_games_and_placement() {
+        let by_games = EnvBuilder::default()
+            .provisional(ProvisionalPolicy::default().min_games(7))
+            .build();
+        assert_eq!(
+            by_games.games_until_established(&Rating::default(), 2),
+            Ok(Some(5))
+        );
+
+        let sigma = ProvisionalPolicy::default().max_sigma(5.0);
+        let plain = EnvBuilder::default().provisional(sigma.clone()).build();
+        let placed = EnvBuilder::default()
+            .provisional(sigma.clone().placement(5, 3.0))
+            .build();
+        let rating = plain.new_rating();
+        assert!(
+            placed.games_until_established(&rating, 0).unwrap().unwrap()
+                < plain.games_until_established(&rating, 0).unwrap().unwrap()
+        );
+
+        let frozen = EnvBuilder::default()
+            .provisional(sigma.placement(MAX_SIMULATED_GAMES, 0.0))
+            .build();
+        assert_eq!(frozen.games_until_established(&rating, 0), Ok(None));
+
+        // without max_sigma a frozen sigma does not matter, only the games left do
+        let counted = EnvBuilder::default()
+            .provisional(ProvisionalPolicy::default().min_games(5).placement(3, 0.0))
+            .build();
+        assert_eq!(counted.games_until_established(&rating, 0), Ok(Some(5)));
+
+        // and once sigma is low enough the remaining min_games are added on top
+        let both = EnvBuilder::default()
+            .provisional(ProvisionalPolicy::default().max_sigma(10.0).min_games(5))
+            .build();
+        assert_eq!(both.games_until_established(&rating, 2), Ok(Some(3)));
+    }
+
+    #[test]
+    fn games_until_established_rejects_invalid_policy() {
+        let env = EnvBuilder::default()
+            .provisional(ProvisionalPolicy::default().max_sigma(-1.0))
+            .build();
+        assert_eq!(
+            env.games_until_established(&env.new_rating(), 0),
+            Err(OpenSkillError::InvalidArgument(
+                "max_sigma must be positive"
+            ))
+        );
+    }
+
+    #[test]
+    fn status_feeds_leaderboard() {
+        let env = EnvBuilder::default()
+            .provisional(ProvisionalPolicy::default().min_games(1))
+            .build();
+        let mut board: Leaderboard<&str> = env.leaderboard().exclude_provisional();
+        let rating = env.new_rating();
+        board.update("new", &rating, env.is_provisional(&rating, 0));
+        board.update("old", &rating, env.is_provisional(&rating, 1));
+        assert_eq!(board.len(), 1);
+        assert!(board.get(&"old").is_some());
+        assert!(board.get(&"new").is_none());
+    }
+}
@@ -0,0 +1,382 @@
+//! PyO3 module `openskill_rs`, named after openskill.py so notebooks can switch with
+//! `from openskill_rs import PlackettLuce`.
+
+use crate::{
+    constant::{DEFAULT_MU, DEFAULT_SIGMA, DEFAULT_Z},
+    env::Env,
+    env_builder::EnvBuilder,
+    error::OpenSkillError,
+    model::kind::ModelKind,
+    rating::{GameResult, Rating},
+    validate::validate_unique,
+};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+impl From<OpenSkillError> for PyErr {
+    fn from(err: OpenSkillError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+#[pyclass(name = "Rating", module = "openskill_rs")]
+#[derive(Debug, Clone)]
+pub struct PyRating {
+    #[pyo3(get, set)]
+    pub mu: f64,
+    #[pyo3(get, set)]
+    pub sigma: f64,
+}
+
+impl From<Rating> for PyRating {
+    fn from(rating: Rating) -> Self {
+        Self {
+            mu: rating.mu,
+            sigma: rating.sigma,
+        }
+    }
+}
+
+impl From<&PyRating> for Rating {
+    fn from(rating: &PyRating) -> Self {
+        Rating::new(rating.mu, rating.sigma)
+    }
+}
+
+#[pymethods]
+impl PyRating {
+    #[new]
+    #[pyo3(signature = (mu = DEFAULT_MU, sigma = DEFAULT_SIGMA))]
+    fn new(mu: f64, sigma: f64) -> Self {
+        Self { mu, sigma }
+    }
+
+    /// Same as openskill.py: `alpha * ((mu - z * sigma) + target / alpha)`.
+    #[pyo3(signature = (z = DEFAULT_Z, alpha = 1.0, target = 0.0))]
+    fn ordinal(&self, z: f64, alpha: f64, target: f64) -> f64 {
+        alpha * ((self.mu - z * self.sigma) + target / alpha)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Rating(mu={}, sigma={})", self.mu, self.sigma)
+    }
+}
+
+fn to_teams(teams: &[Vec<PyRating>]) -> Vec<Vec<Rating>> {
+    teams
+        .iter()
+        .map(|team| team.iter().map(Rating::from).collect())
+        .collect()
+}
+
+fn from_teams(teams: Vec<Vec<Rating>>) -> Vec<Vec<PyRating>> {
+    teams
+        .into_iter()
+        .map(|team| team.into_iter().map(PyRating::from).collect())
+        .collect()
+}
+
+/// openskill.py ranks default to the order of `teams`.
+fn game(teams: Vec<Vec<Rating>>, ranks: Option<Vec<usize>>) -> GameResult {
+    let ranks = ranks.unwrap_or_else(|| (1..=teams.len()).collect());
+    GameResult::new(teams, ranks)
+}
+
+fn rate(
+    env: &Env,
+    result: &GameResult,
+    tau: Option<f64>,
+) -> crate::error::Result<Vec<Vec<Rating>>> {
+    match tau {
+        Some(tau) => env.rate_with_tau(result, tau),
+        None => env.rate(result),
+    }
+}
+
+/// `(teams of player keys, ranks)` as accepted by `Env.rate_many`.
+type KeyedGame<'py> = (Vec<Vec<Bound<'py, PyAny>>>, Option<Vec<usize>>);
+
+#[pyclass(name = "EnvBuilder", module = "openskill_rs")]
+#[derive(Default)]
+pub struct PyEnvBuilder(EnvBuilder);
+
+#[pymethods]
+impl PyEnvBuilder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn model<'py>(mut slf: PyRefMut<'py, Self>, model: &str) -> PyResult<PyRefMut<'py, Self>> {
+        slf.0 = slf.0.clone().model(model.parse::<ModelKind>()?);
+        Ok(slf)
+    }
+
+    fn mu(mut slf: PyRefMut<'_, Self>, mu: f64) -> PyRefMut<'_, Self> {
+        slf.0 = slf.0.clone().mu(mu);
+        slf
+    }
+
+    fn sigma(mut slf: PyRefMut<'_, Self>, sigma: f64) -> PyRefMut<'_, Self> {
+        slf.0 = slf.0.clone().sigma(sigma);
+        slf
+    }
+
+    fn beta(mut slf: PyRefMut<'_, Self>, beta: f64) -> PyRefMut<'_, Self> {
+        slf.0 = slf.0.clone().beta(beta);
+        slf
+    }
+
+    fn kappa(mut slf: PyRefMut<'_, Self>, kappa: f64) -> PyRefMut<'_, Self> {
+        slf.0 = slf.0.clone().kappa(kappa);
+        slf
+    }
+
+    fn z(mut slf: PyRefMut<'_, Self>, z: f64) -> PyRefMut<'_, Self> {
+        slf.0 = slf.0.clone().z(z);
+        slf
+    }
+
+    fn build(&self) -> PyEnv {
+        PyEnv(self.0.clone().build())
+    }
+}
+
+#[pyclass(name = "Env", module = "openskill_rs", frozen)]
+pub struct PyEnv(Env);
+
+#[pymethods]
+impl PyEnv {
+    #[new]
+    #[pyo3(signature = (model = "PlackettLuce", mu = None, sigma = None, beta = None, kappa = None, z = None))]
+    fn new(
+        model: &str,
+        mu: Option<f64>,
+        sigma: Option<f64>,
+        beta: Option<f64>,
+        kappa: Option<f64>,
+        z: Option<f64>,
+    ) -> PyResult<Self> {
+        let mut builder = EnvBuilder::default().model(model.parse()?);
+        for (value, set) in [
+            (mu, EnvBuilder::mu as fn(EnvBuilder, f64) -> EnvBuilder),
+            (sigma, EnvBuilder::sigma),
+            (beta, EnvBuilder::beta),
+            (kappa, EnvBuilder::kappa),
+            (z, EnvBuilder::z),
+        ] {
+            if let Some(value) = value {
+                builder = set(builder, value);
+            }
+        }
+        Ok(Self(builder.build()))
+    }
+
+    #[pyo3(signature = (mu = None, sigma = None))]
+    fn rating(&self, mu: Option<f64>, sigma: Option<f64>) -> PyRating {
+        let default = self.0.new_rating();
+        PyRating {
+            mu: mu.unwrap_or(default.mu),
+            sigma: sigma.unwrap_or(default.sigma),
+        }
+    }
+
+    #[pyo3(signature = (teams, ranks = None, tau = None))]
+    fn rate(
+        &self,
+        teams: Vec<Vec<PyRating>>,
+        ranks: Option<Vec<usize>>,
+        tau: Option<f64>,
+    ) -> PyResult<Vec<Vec<PyRating>>> {
+        let result = game(to_teams(&teams), ranks);
+        Ok(from_teams(rate(&self.0, &result, tau)?))
+    }
+
+    fn predict_win(&self, teams: Vec<Vec<PyRating>>) -> PyResult<Vec<f64>> {
+        Ok(self.0.predict_win(&to_teams(&teams))?)
+    }
+
+    fn predict_draw(&self, teams: Vec<Vec<PyRating>>) -> PyResult<f64> {
+        Ok(self.0.predict_draw(&to_teams(&teams))?)
+    }
+
+    fn ordinal(&self, rating: PyRating) -> f64 {
+        self.0.ordinal(&Rating::from(&rating))
+    }
+
+    /// Replays `games` in order, each a `(teams, ranks)` pair whose teams hold player keys,
+    /// starting from `ratings` (unknown players get the default rating). Keys are resolved
+    /// with the GIL held; the replay itself releases it. Returns the final `{key: Rating}`.
+    #[pyo3(signature = (games, ratings = None, tau = None))]
+    fn rate_many<'py>(
+        &self,
+        py: Python<'py>,
+        games: Vec<KeyedGame<'py>>,
+        ratings: Option<Bound<'py, PyDict>>,
+        tau: Option<f64>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let slots = PyDict::new(py);
+        let mut keys = vec![];
+        let mut state: Vec<Rating> = vec![];
+        let mut slot = |key: &Bound<'py, PyAny>, rating: Option<Rating>| -> PyResult<usize> {
+            if let Some(slot) = slots.get_item(key)? {
+                return slot.extract();
+            }
+            slots.set_item(key, state.len())?;
+            keys.push(key.clone());
+            state.push(rating.unwrap_or_else(|| self.0.new_rating()));
+            Ok(state.len() - 1)
+        };
+        if let Some(ratings) = &ratings {
+            for (key, rating) in ratings {
+                slot(&key, Some(Rating::from(&rating.extract::<PyRating>()?)))?;
+            }
+        }
+        let games = games
+            .iter()
+            .map(|(teams, ranks)| {
+                let teams = teams
+                    .iter()
+                    .map(|team| team.iter().map(|key| slot(key, None)).collect())
+                    .collect::<PyResult<Vec<Vec<usize>>>>()?;
+                validate_unique(
+                    teams.iter().flatten(),
+                    "a player can only appear once per match",
+                )?;
+                Ok((teams, ranks.clone()))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let env = &self.0;
+        let state = py.allow_threads(move || {
+            for (teams, ranks) in games {
+                let ratings = teams
+                    .iter()
+                    .map(|team| team.iter().map(|p| state[*p].clone()).collect())
+                    .collect();
+                let rated = rate(env, &game(ratings, ranks), tau)?;
+                for (team, rated) in teams.iter().zip(rated) {
+                    for (p, rating) in team.iter().zip(rated) {
+                        state[*p] = rating;
+                    }
+                }
+            }
+            Ok::<_, OpenSkillError>(state)
+        })?;
+
+        let out = PyDict::new(py);
+        for (key, rating) in keys.iter().zip(state) {
+            out.set_item(key, PyRating::from(rating))?;
+        }
+        Ok(out)
+    }
+}
+
+macro_rules! model_constructor {
+    ($name:ident, $kind:literal) => {
+        /// Mirrors the openskill.py model class of the same name.
+        #[pyfunction]
+        #[pyo3(name = $kind, signature = (mu = None, sigma = None, beta = None, kappa = None, z = None))]
+        fn $name(
+            mu: Option<f64>,
+            sigma: Option<f64>,
+            beta: Option<f64>,
+            kappa: Option<f64>,
+            z: Option<f64>,
+        ) -> PyResult<PyEnv> {
+            PyEnv::new($kind, mu, sigma, beta, kappa, z)
+        }
+    };
+}
+
+model_constructor!(plackett_luce, "PlackettLuce");
+model_constructor!(bradley_terry_part, "BradleyTerryPart");
+model_constructor!(bradley_terry_full, "BradleyTerryFull");
+model_constructor!(thurstone_mosteller_part, "ThurstoneMostellerPart");
+model_constructor!(thurstone_mosteller_full, "ThurstoneMostellerFull");
+
+#[pymodule]
+fn openskill_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyRating>()?;
+    m.add_class::<PyEnvBuilder>()?;
+    m.add_class::<PyEnv>()?;
+    m.add_function(wrap_pyfunction!(plackett_luce, m)?)?;
+    m.add_function(wrap_pyfunction!(bradley_terry_part, m)?)?;
+    m.add_function(wrap_pyfunction!(bradley_terry_full, m)?)?;
+    m.add_function(wrap_pyfunction!(thurstone_mosteller_part, m)?)?;
+    m.add_function(wrap_pyfunction!(thurstone_mosteller_full, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pyo3::{ffi::c_str, types::PyModule};
+
+    fn run(code: &std::ffi::CStr) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "openskill_rs").unwrap();
+            openskill_rs(&module).unwrap();
+            let modules = py.import("sys").unwrap().getattr("modules").unwrap();
+            modules.set_item("openskill_rs", module).unwrap();
+            if let Err(err) = py.run(code, None, None) {
+                err.print(py);
+                panic!("python test failed");
+            }
+        });
+    }
+
+    #[test]
+    fn python_api_matches_rust() {
+        run(c_str!(
+            r#"
+from openskill_rs import Env, EnvBuilder, PlackettLuce, Rating
+model = PlackettLuce()
+a, b = model.rating(), model.rating()
+[[a2], [b2]] = model.rate([[a], [b]])
+assert abs(a2.mu - 27.63523138347365) < 1e-9, a2
+assert abs(b2.mu - 22.36476861652635) < 1e-9, b2
+[[b3], [a3]] = model.rate([[b], [a]], ranks=[2, 1])
+assert abs(a3.mu - a2.mu) < 1e-12
+assert model.predict_win([[a], [b]]) == [0.5, 0.5]
+assert 0 < model.predict_draw([[a], [b]]) < 1
+assert abs(model.ordinal(a) - a.ordinal()) < 1e-12
+assert EnvBuilder().model("BradleyTerryFull").mu(30.0).build().rating().mu == 30.0
+assert Env(model="ThurstoneMostellerPart", sigma=5.0).rating().sigma == 5.0
+try:
+    Env(model="Glicko")
+    raise AssertionError("unknown model accepted")
+except ValueError:
+    pass
+"#
+        ));
+    }
+
+    #[test]
+    fn rate_many_replays_in_order() {
+        run(c_str!(
+            r#"
+from openskill_rs import PlackettLuce, Rating
+model = PlackettLuce()
+games = [([["a"], ["b"]], None), ([["b"], ["c"]], [2, 1]), ([["a", "c"], ["b"]], None)]
+got = model.rate_many(games, ratings={"d": Rating(30.0, 2.0)})
+
+ratings = {}
+for teams, ranks in games:
+    current = [[ratings.get(p, model.rating()) for p in team] for team in teams]
+    for team, rated in zip(teams, model.rate(current, ranks=ranks)):
+        ratings.update(zip(team, rated))
+assert sorted(got) == ["a", "b", "c", "d"]
+for key, rating in ratings.items():
+    assert abs(got[key].mu - rating.mu) < 1e-12, key
+    assert abs(got[key].sigma - rating.sigma) < 1e-12, key
+assert got["d"].mu == 30.0
+try:
+    model.rate_many([([["a"], ["b", "a"]], None)])
+    raise AssertionError("repeated player accepted")
+except ValueError:
+    pass
+"#
+        ));
+    }
+}
@@ -1,18 +1,24 @@
 use crate::constant::{DEFAULT_MU, DEFAULT_SIGMA};
-use std::{fmt::Display, iter::zip};
+use alloc::{vec, vec::Vec};
+use core::{fmt::Display, iter::zip};
+use num_traits::Float;
 
+/// A player's skill estimate. `F` is the storage type, `f32` halves the memory of large stores;
+/// the rating math itself always runs in `f64`.
 #[derive(Debug, Clone)]
-pub struct Rating {
-    pub mu: f64,
-    pub sigma: f64,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rating<F = f64> {
+    pub mu: F,
+    pub sigma: F,
 }
 
-impl Display for Rating {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<F: Display> Display for Rating<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "(mu: {}, sigma: {})", self.mu, self.sigma)
     }
 }
 
+// Only `f64` has a default so `Rating::default()` keeps inferring; use `Env::new_rating` otherwise.
 impl Default for Rating {
     fn default() -> Self {
         Self {
@@ -22,10 +28,25 @@ impl Default for Rating {
     }
 }
 
-impl Rating {
-    pub fn new(mu: f64, sigma: f64) -> Self {
+impl<F: Float> Rating<F> {
+    pub fn new(mu: F, sigma: F) -> Self {
         Self { mu, sigma }
     }
+
+    pub fn cast<G: Float>(&self) -> Rating<G> {
+        Rating::new(cast(self.mu), cast(self.sigma))
+    }
+}
+
+pub(crate) fn cast<F: Float, G: Float>(value: F) -> G {
+    G::from(value).unwrap_or_else(G::nan)
+}
+
+pub(crate) fn cast_teams<F: Float, G: Float>(teams: &[Vec<Rating<F>>]) -> Vec<Vec<Rating<G>>> {
+    teams
+        .iter()
+        .map(|team| team.iter().map(Rating::cast).collect())
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -34,50 +55,166 @@ pub struct TeamRating {
     pub mu: f64,
     pub sigma_sq: f64,
     pub rank: usize,
+    pub anchored: Vec<bool>,
 }
 
 impl TeamRating {
     pub fn new(members: Vec<Rating>, rank: usize) -> Self {
-        let mu = members.iter().map(|m| m.mu).sum::<f64>();
-        let sigma_sq = members.iter().map(|m| m.sigma.powi(2)).sum::<f64>();
+        Self::with_aggregate(members, rank, sum_aggregate)
+    }
+
+    pub fn with_aggregate(members: Vec<Rating>, rank: usize, aggregate: AggregateFunc) -> Self {
+        let (mu, sigma_sq) = aggregate(&members);
+        let anchored = vec![false; members.len()];
         Self {
             members,
             mu,
             sigma_sq,
             rank,
+            anchored,
         }
     }
 
+    pub fn with_anchored(mut self, anchored: Vec<bool>) -> Self {
+        self.anchored = anchored;
+        self
+    }
+
     pub fn team_size(&self) -> usize {
         self.members.len()
     }
+
+    /// Sum of sigma² over members that are not anchored; the share of an update each free member receives.
+    pub fn free_sigma_sq(&self) -> f64 {
+        zip(&self.members, &self.anchored)
+            .filter(|(_, anchored)| !**anchored)
+            .map(|(m, _)| m.sigma.powi(2))
+            .sum::<f64>()
+    }
 }
 
 pub(crate) fn to_team_ratings(
-    teams: &Vec<Vec<Rating>>,
+    teams: &[Vec<Rating>],
     ranks: Option<Vec<usize>>,
+    aggregate: AggregateFunc,
 ) -> Vec<TeamRating> {
     let ranks = ranks.unwrap_or_else(|| (0..teams.len()).collect());
     zip(teams.iter(), ranks.iter())
-        .map(|(team, rank)| TeamRating::new(team.clone(), *rank))
+        .map(|(team, rank)| TeamRating::with_aggregate(team.clone(), *rank, aggregate))
         .collect()
 }
 
-pub(crate) fn calc_total_player(team_ratings: &Vec<TeamRating>) -> usize {
+pub(crate) fn apply_advantage(team_ratings: &mut [TeamRating], advantage: &[f64]) {
+    zip(team_ratings.iter_mut(), advantage).for_each(|(team, advantage)| team.mu += advantage);
+}
+
+pub(crate) fn calc_total_player(team_ratings: &[TeamRating]) -> usize {
     team_ratings.iter().map(|it| it.team_size()).sum::<usize>()
 }
 
-pub struct GameResult {
-    pub teams: Vec<Vec<Rating>>,
+#[derive(Debug, Clone)]
+pub struct GameResult<F = f64> {
+    pub teams: Vec<Vec<Rating<F>>>,
     pub ranks: Vec<usize>,
+    /// `(team, player)` indices into `teams` whose ratings are fixed and never updated.
+    pub anchored: Vec<(usize, usize)>,
+    /// Importance of the game; scales the size of every update, 1.0 is a regular game.
+    pub weight: f64,
+    /// Per team slot shift of the effective team mu, e.g. a first-mover edge. Empty means none.
+    pub advantage: Vec<f64>,
+}
+
+impl<F: Float> GameResult<F> {
+    pub fn new(teams: Vec<Vec<Rating<F>>>, ranks: Vec<usize>) -> Self {
+        Self {
+            teams,
+            ranks,
+            anchored: vec![],
+            weight: 1.0,
+            advantage: vec![],
+        }
+    }
+
+    pub fn advantage(mut self, advantage: Vec<f64>) -> Self {
+        self.advantage = advantage;
+        self
+    }
+
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn anchor(mut self, team: usize, player: usize) -> Self {
+        self.anchored.push((team, player));
+        self
+    }
+
+    pub fn is_anchored(&self, team: usize, player: usize) -> bool {
+        self.anchored.contains(&(team, player))
+    }
+
+    pub fn cast<G: Float>(&self) -> GameResult<G> {
+        GameResult {
+            teams: cast_teams(&self.teams),
+            ranks: self.ranks.clone(),
+            anchored: self.anchored.clone(),
+            weight: self.weight,
+            advantage: self.advantage.clone(),
+        }
+    }
 }
 
 impl GameResult {
-    pub fn new(teams: Vec<Vec<Rating>>, ranks: Vec<usize>) -> Self {
-        Self { teams, ranks }
+    pub(crate) fn team_ratings(&self, aggregate: AggregateFunc) -> Vec<TeamRating> {
+        let mut team_ratings = to_team_ratings(&self.teams, Some(self.ranks.clone()), aggregate);
+        apply_advantage(&mut team_ratings, &self.advantage);
+        team_ratings
+            .into_iter()
+            .enumerate()
+            .map(|(i, team)| {
+                let anchored = (0..team.team_size())
+                    .map(|j| self.is_anchored(i, j))
+                    .collect();
+                team.with_anchored(anchored)
+            })
+            .collect()
     }
 }
 
+/// Combines the members of a team into the team `(mu, sigma²)`.
+pub type AggregateFunc = fn(&[Rating]) -> (f64, f64);
+
+/// Team strength is the sum of its members; a 3 player team is 3 times a solo player.
+pub fn sum_aggregate(members: &[Rating]) -> (f64, f64) {
+    let mu = members.iter().map(|m| m.mu).sum::<f64>();
+    let sigma_sq = members.iter().map(|m| m.sigma.powi(2)).sum::<f64>();
+    (mu, sigma_sq)
+}
+
+/// Team strength is the average member, so uneven team sizes are compared fairly.
+pub fn mean_aggregate(members: &[Rating]) -> (f64, f64) {
+    let n = members.len() as f64;
+    let (mu, sigma_sq) = sum_aggregate(members);
+    (mu / n, sigma_sq / n.powi(2))
+}
+
+/// Team strength is its strongest member.
+pub fn max_aggregate(members: &[Rating]) -> (f64, f64) {
+    members
+        .iter()
+        .max_by(|a, b| a.mu.total_cmp(&b.mu))
+        .map_or((0.0, 0.0), |m| (m.mu, m.sigma.powi(2)))
+}
+
+/// Team strength is its weakest member.
+pub fn min_aggregate(members: &[Rating]) -> (f64, f64) {
+    members
+        .iter()
+        .min_by(|a, b| a.mu.total_cmp(&b.mu))
+        .map_or((0.0, 0.0), |m| (m.mu, m.sigma.powi(2)))
+}
+
 pub type GammaFunc = fn(f64, f64, team: &TeamRating) -> f64;
 
 pub fn default_gamma(c: f64, _: f64, team: &TeamRating) -> f64 {
@@ -90,23 +227,186 @@ pub fn default_ordinal(rating: &Rating, z: f64) -> f64 {
     rating.mu - (z * rating.sigma)
 }
 
-pub fn ladder_pairs<T>(ranks: &Vec<T>) -> Vec<Vec<T>>
+pub fn ladder_pairs<T>(ranks: &[T]) -> Vec<Vec<T>>
 where
     T: Clone,
 {
-    let o_ranks: Vec<Option<T>> = ranks.clone().into_iter().map(Some).collect();
+    let o_ranks: Vec<Option<T>> = ranks.iter().cloned().map(Some).collect();
     let left: Vec<_> = [None]
         .iter()
         .cloned()
         .chain(o_ranks[..o_ranks.len() - 1].iter().cloned())
         .collect();
-    let right = vec![&o_ranks[1..], &[None]].concat();
-    return zip(left, right)
+    let right = [&o_ranks[1..], &[None]].concat();
+    zip(left, right)
         .map(|(l, r)| match (l, r) {
             (Some(l), Some(r)) => vec![l, r],
             (Some(l), None) => vec![l],
             (None, Some(r)) => vec![r],
             _ => vec![],
         })
-        .collect();
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        env::test::{for_each_model, teams},
+        env_builder::EnvBuilder,
+        error::OpenSkillError,
+    };
+    use approx::relative_eq;
+
+    #[test]
+    fn anchored_player_is_unchanged_and_share_goes_to_teammates() {
+        let bot = Rating::new(30.0, 8.0);
+        let teams = vec![
+            vec![Rating::default(), bot.clone()],
+            vec![Rating::default(), Rating::default()],
+        ];
+        for_each_model(|builder| {
+            let env = builder.build();
+            let free = env
+                .rate(&GameResult::new(teams.clone(), vec![1, 2]))
+                .unwrap();
+            let anchored = env
+                .rate(&GameResult::new(teams.clone(), vec![1, 2]).anchor(0, 1))
+                .unwrap();
+
+            assert_eq!(anchored[0][1].mu, bot.mu);
+            assert_eq!(anchored[0][1].sigma, bot.sigma);
+            let human_sigma_sq = Rating::default().sigma.powi(2);
+            let share = human_sigma_sq / (human_sigma_sq + bot.sigma.powi(2));
+            let free_gain = free[0][0].mu - teams[0][0].mu;
+            let anchored_gain = anchored[0][0].mu - teams[0][0].mu;
+            assert!(relative_eq!(
+                free_gain / share,
+                anchored_gain,
+                epsilon = 1e-9
+            ));
+            assert!(relative_eq!(
+                free[1][0].mu,
+                anchored[1][0].mu,
+                epsilon = 1e-9
+            ));
+        });
+    }
+
+    #[test]
+    fn fully_anchored_team_is_unchanged() {
+        let teams = vec![vec![Rating::new(40.0, 1.0)], vec![Rating::default()]];
+        let env = EnvBuilder::default().build();
+        let got = env
+            .rate_with_tau(&GameResult::new(teams, vec![2, 1]).anchor(0, 0), 0.5)
+            .unwrap();
+        assert_eq!(got[0][0].mu, 40.0);
+        assert_eq!(got[0][0].sigma, 1.0);
+    }
+
+    #[test]
+    fn anchored_out_of_range_is_error() {
+        let teams = vec![vec![Rating::default()], vec![Rating::default()]];
+        let got = EnvBuilder::default()
+            .build()
+            .rate(&GameResult::new(teams, vec![1, 2]).anchor(1, 1));
+        assert_eq!(
+            got.unwrap_err(),
+            OpenSkillError::InvalidArgument("anchored player is out of range")
+        );
+    }
+
+    #[test]
+    fn unit_weight_reproduces_unweighted() {
+        for_each_model(|builder| {
+            let env = builder.build();
+            let plain = env.rate(&GameResult::new(teams(), vec![2, 1, 3])).unwrap();
+            let weighted = env
+                .rate(&GameResult::new(teams(), vec![2, 1, 3]).weight(1.0))
+                .unwrap();
+            for (p, w) in plain.iter().flatten().zip(weighted.iter().flatten()) {
+                assert_eq!(p.mu, w.mu);
+                assert_eq!(p.sigma, w.sigma);
+            }
+        });
+    }
+
+    #[test]
+    fn weight_scales_mu_change() {
+        for_each_model(|builder| {
+            let env = builder.build();
+            let plain = env.rate(&GameResult::new(teams(), vec![2, 1, 3])).unwrap();
+            let half = env
+                .rate(&GameResult::new(teams(), vec![2, 1, 3]).weight(0.5))
+                .unwrap();
+            let zero = env
+                .rate(&GameResult::new(teams(), vec![2, 1, 3]).weight(0.0))
+                .unwrap();
+            for ((before, p), (h, z)) in teams()
+                .iter()
+                .flatten()
+                .zip(plain.iter().flatten())
+                .zip(half.iter().flatten().zip(zero.iter().flatten()))
+            {
+                let want = (p.mu - before.mu) * 0.5;
+                assert!(relative_eq!(h.mu - before.mu, want, epsilon = 1e-9));
+                assert!(h.sigma >= p.sigma);
+                assert_eq!(z.mu, before.mu);
+                assert_eq!(z.sigma, before.sigma);
+            }
+        });
+    }
+
+    #[test]
+    fn negative_weight_is_error() {
+        let got = EnvBuilder::default()
+            .build()
+            .rate(&GameResult::new(teams(), vec![1, 2, 3]).weight(-1.0));
+        assert_eq!(
+            got.unwrap_err(),
+            OpenSkillError::InvalidArgument("weight must be a finite non-negative number")
+        );
+    }
+
+    fn handicap_teams() -> Vec<Vec<Rating>> {
+        vec![vec![Rating::default(); 2], vec![Rating::default(); 3]]
+    }
+
+    #[test]
+    fn mean_aggregate_treats_uneven_teams_as_equal() {
+        let env = EnvBuilder::default().aggregate(mean_aggregate).build();
+        let win = env.predict_win(&handicap_teams()).unwrap();
+        assert!(relative_eq!(win[0], 0.5, epsilon = 1e-12));
+
+        let summed = EnvBuilder::default().build();
+        let win = summed.predict_win(&handicap_teams()).unwrap();
+        assert!(win[1] > 0.8);
+    }
+
+    #[test]
+    fn weakest_and_strongest_link() {
+        let teams = vec![
+            vec![Rating::new(40.0, 2.0), Rating::new(10.0, 2.0)],
+            vec![Rating::new(25.0, 2.0)],
+        ];
+        let weakest = EnvBuilder::default().aggregate(min_aggregate).build();
+        let strongest = EnvBuilder::default().aggregate(max_aggregate).build();
+        assert!(weakest.predict_win(&teams).unwrap()[0] < 0.5);
+        assert!(strongest.predict_win(&teams).unwrap()[0] > 0.5);
+    }
+
+    #[test]
+    fn custom_aggregate_is_used_by_every_model() {
+        fn first_member(members: &[Rating]) -> (f64, f64) {
+            (members[0].mu, members[0].sigma.powi(2))
+        }
+        for_each_model(|builder| {
+            let custom = builder.clone().aggregate(first_member).build();
+            let result = GameResult::new(handicap_teams(), vec![1, 2]);
+            let got = custom.rate(&result).unwrap();
+            let sum = builder.build().rate(&result).unwrap();
+            assert!(got[0][0].mu > 25.0 && got[1][0].mu < 25.0);
+            assert!((got[0][0].mu - sum[0][0].mu).abs() > 1e-6);
+        });
+    }
 }
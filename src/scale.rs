@@ -0,0 +1,249 @@
+use crate::{
+    error::{OpenSkillError, Result},
+    math::{self, normal},
+    predict_win::predict_win,
+    rating::Rating,
+};
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// How a `RatingScale` is laid out on the display scale; see `Env::rating_scale`.
+#[derive(Debug, Clone, Default)]
+pub struct RatingScaleBuilder {
+    center: Option<f64>,
+    odds_points: Option<f64>,
+    range: Option<(f64, f64)>,
+    reference_sigma: Option<f64>,
+}
+
+impl RatingScaleBuilder {
+    /// Elo like: a new player shows 1500 and 400 points are 10:1 odds.
+    pub fn elo() -> Self {
+        Self::default().center(1500.0).odds_points(400.0)
+    }
+
+    /// 0 to 5000 over the span of a new player's mu +- z * sigma.
+    pub fn mmr() -> Self {
+        Self::default().range(0.0, 5000.0)
+    }
+
+    /// Displayed value of the default mu.
+    pub fn center(mut self, center: f64) -> Self {
+        self.center = Some(center);
+        self.range = None;
+        self
+    }
+
+    /// Display points between two players when the better one is a 10:1 favourite.
+    pub fn odds_points(mut self, odds_points: f64) -> Self {
+        self.odds_points = Some(odds_points);
+        self.range = None;
+        self
+    }
+
+    /// Maps default mu - z * default sigma to `low` and default mu + z * default sigma to `high`.
+    pub fn range(mut self, low: f64, high: f64) -> Self {
+        self.range = Some((low, high));
+        self.center = None;
+        self.odds_points = None;
+        self
+    }
+
+    /// Sigma of the two players for which `odds_points` is exact, 0.0 (the default) meaning
+    /// fully established players. More uncertain players are closer to even odds.
+    pub fn reference_sigma(mut self, reference_sigma: f64) -> Self {
+        self.reference_sigma = Some(reference_sigma);
+        self
+    }
+
+    pub(crate) fn build(
+        self,
+        default_mu: f64,
+        default_sigma: f64,
+        z: f64,
+        beta: f64,
+    ) -> Result<RatingScale> {
+        let reference_sigma = self.reference_sigma.unwrap_or(0.0);
+        if !reference_sigma.is_finite() || reference_sigma < 0.0 {
+            return Err(OpenSkillError::InvalidArgument(
+                "reference_sigma must be a finite non-negative number",
+            ));
+        }
+        // mu difference at which a 1v1 between two reference players is 10:1
+        let odds_mu = normal().inverse_cdf(10.0 / 11.0)
+            * math::sigma_bar(
+                reference_sigma.powi(2),
+                reference_sigma.powi(2),
+                beta.powi(2),
+                2,
+            );
+
+        let (center, slope) = match self.range {
+            Some((low, high)) => ((low + high) / 2.0, (high - low) / (2.0 * z * default_sigma)),
+            None => (
+                self.center.unwrap_or(1500.0),
+                self.odds_points.unwrap_or(400.0) / odds_mu,
+            ),
+        };
+        if !center.is_finite() || !slope.is_finite() || slope <= 0.0 {
+            return Err(OpenSkillError::InvalidArgument(
+                "scale must map to an increasing finite range",
+            ));
+        }
+
+        Ok(RatingScale {
+            center,
+            slope,
+            default_mu,
+            z,
+            beta,
+            odds_mu,
+        })
+    }
+}
+
+/// A rating on the display scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayRating {
+    /// Displayed mu.
+    pub value: f64,
+    /// Displayed sigma, in points.
+    pub deviation: f64,
+    /// Displayed ordinal, `value - z * deviation`.
+    pub ordinal: f64,
+}
+
+/// Linear map between `Rating` and a familiar display scale, with an exact inverse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingScale {
+    center: f64,
+    slope: f64,
+    default_mu: f64,
+    z: f64,
+    beta: f64,
+    odds_mu: f64,
+}
+
+impl RatingScale {
+    /// Display points of a value on the mu scale, e.g. mu itself or an ordinal.
+    pub fn points(&self, mu: f64) -> f64 {
+        self.center + (mu - self.default_mu) * self.slope
+    }
+
+    /// Inverse of `points`.
+    pub fn mu(&self, points: f64) -> f64 {
+        self.default_mu + (points - self.center) / self.slope
+    }
+
+    pub fn display(&self, rating: &Rating) -> DisplayRating {
+        DisplayRating {
+            value: self.points(rating.mu),
+            deviation: rating.sigma * self.slope,
+            ordinal: self.points(rating.mu - self.z * rating.sigma),
+        }
+    }
+
+    /// Inverse of `display`, for ratings entered on the display scale.
+    pub fn rating(&self, value: f64, deviation: f64) -> Rating {
+        Rating::new(self.mu(value), deviation / self.slope)
+    }
+
+    /// Display points between two players when the better one is a 10:1 favourite.
+    pub fn odds_points(&self) -> f64 {
+        self.odds_mu * self.slope
+    }
+
+    /// Win probability of `a` against `b`, as `Env::predict_win` gives it without advantage.
+    pub fn predict_win(&self, a: &DisplayRating, b: &DisplayRating) -> f64 {
+        let teams = vec![
+            vec![self.rating(a.value, a.deviation)],
+            vec![self.rating(b.value, b.deviation)],
+        ];
+        predict_win(&teams, self.beta).map_or(f64::NAN, |it| it[0])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::env_builder::EnvBuilder;
+    use approx::relative_eq;
+
+    #[test]
+    fn elo_scale_has_ten_to_one_odds_at_400_points() {
+        let env = EnvBuilder::default().build();
+        let scale = env.rating_scale(RatingScaleBuilder::elo()).unwrap();
+        assert!(relative_eq!(scale.points(25.0), 1500.0));
+        assert!(relative_eq!(scale.odds_points(), 400.0));
+
+        let a = scale.rating(1900.0, 0.0);
+        let b = scale.rating(1500.0, 0.0);
+        let p = env
+            .predict_win(&[vec![a.clone()], vec![b.clone()]])
+            .unwrap()[0];
+        assert!(relative_eq!(p, 10.0 / 11.0, epsilon = 1e-9), "{p}");
+        assert!(relative_eq!(
+            scale.predict_win(&scale.display(&a), &scale.display(&b)),
+            p,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[test]
+    fn reference_sigma_keeps_odds_for_uncertain_players() {
+        let env = EnvBuilder::default().build();
+        let scale = env
+            .rating_scale(RatingScaleBuilder::elo().reference_sigma(4.0))
+            .unwrap();
+        let deviation = 4.0 * scale.display(&Rating::new(0.0, 1.0)).deviation;
+        let a = scale.rating(1700.0, deviation);
+        let b = scale.rating(1300.0, deviation);
+        let p = env.predict_win(&[vec![a], vec![b]]).unwrap()[0];
+        assert!(relative_eq!(p, 10.0 / 11.0, epsilon = 1e-9), "{p}");
+    }
+
+    #[test]
+    fn mmr_scale_spans_new_player_range() {
+        let env = EnvBuilder::default().build();
+        let scale = env.rating_scale(RatingScaleBuilder::mmr()).unwrap();
+        let shown = scale.display(&env.new_rating());
+        assert!(relative_eq!(shown.value, 2500.0));
+        assert!(relative_eq!(shown.ordinal, 0.0, epsilon = 1e-9));
+        assert!(relative_eq!(shown.value + 3.0 * shown.deviation, 5000.0));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let env = EnvBuilder::default().z(2.0).build();
+        for builder in [RatingScaleBuilder::elo(), RatingScaleBuilder::mmr()] {
+            let scale = env.rating_scale(builder).unwrap();
+            for rating in [Rating::new(31.7, 2.3), Rating::new(-4.0, 9.0)] {
+                let shown = scale.display(&rating);
+                assert!(relative_eq!(
+                    shown.ordinal,
+                    scale.points(env.ordinal(&rating)),
+                    epsilon = 1e-9
+                ));
+                let back = scale.rating(shown.value, shown.deviation);
+                assert!(relative_eq!(back.mu, rating.mu, epsilon = 1e-12));
+                assert!(relative_eq!(back.sigma, rating.sigma, epsilon = 1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_scale_is_error() {
+        let env = EnvBuilder::default().build();
+        assert!(env
+            .rating_scale(RatingScaleBuilder::default().range(10.0, 0.0))
+            .is_err());
+        assert!(env
+            .rating_scale(RatingScaleBuilder::default().odds_points(-400.0))
+            .is_err());
+        assert!(env
+            .rating_scale(RatingScaleBuilder::elo().reference_sigma(f64::NAN))
+            .is_err());
+    }
+}
@@ -0,0 +1,397 @@
+use crate::{
+    error::{OpenSkillError, Result},
+    rating::{GameResult, Rating},
+    validate::validate_match,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use num_traits::Float;
+
+/// A game of a history to smooth: player ids per team, their ranks and when it was played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedResult<K> {
+    pub time: f64,
+    pub teams: Vec<Vec<K>>,
+    pub ranks: Vec<usize>,
+    pub weight: f64,
+}
+
+impl<K> TimedResult<K> {
+    pub fn new(time: f64, teams: Vec<Vec<K>>, ranks: Vec<usize>) -> Self {
+        Self {
+            time,
+            teams,
+            ranks,
+            weight: 1.0,
+        }
+    }
+
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Estimated skill of a player at the time of one of their games.
+#[derive(Debug, Clone)]
+pub struct SkillPoint<F = f64> {
+    pub time: f64,
+    pub rating: Rating<F>,
+}
+
+impl<F: Float> SkillPoint<F> {
+    pub fn cast<G: Float>(&self) -> SkillPoint<G> {
+        SkillPoint {
+            time: self.time,
+            rating: self.rating.cast(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SmoothPolicy {
+    tau: Option<f64>,
+    iterations: Option<usize>,
+    tolerance: Option<f64>,
+}
+
+impl SmoothPolicy {
+    /// Skill drift per unit of time: sigma² grows by `tau² * elapsed` between two games.
+    /// Defaults to a hundredth of the env's default sigma; 0.0 means skill never changes.
+    pub fn tau(mut self, tau: f64) -> Self {
+        self.tau = Some(tau);
+        self
+    }
+
+    /// Upper bound on forward-backward sweeps, 16 by default.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Stops once no mu or sigma moved more than this in a sweep, 1e-6 by default.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+}
+
+/// A Gaussian in natural parameters, so messages multiply by adding.
+#[derive(Debug, Clone, Copy, Default)]
+struct Message {
+    pi: f64,
+    tau: f64,
+}
+
+impl Message {
+    fn from_rating(rating: &Rating) -> Self {
+        let pi = rating.sigma.powi(-2);
+        Self {
+            pi,
+            tau: pi * rating.mu,
+        }
+    }
+
+    fn rating(self) -> Rating {
+        Rating::new(self.tau / self.pi, self.pi.powf(-0.5))
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            pi: self.pi + other.pi,
+            tau: self.tau + other.tau,
+        }
+    }
+
+    /// `self / other`, dropping any part that would not be a proper Gaussian.
+    fn div(self, other: Self) -> Self {
+        let pi = self.pi - other.pi;
+        if pi <= 0.0 {
+            return Self::default();
+        }
+        Self {
+            pi,
+            tau: self.tau - other.tau,
+        }
+    }
+
+    fn drift(self, variance: f64) -> Self {
+        if self.pi == 0.0 || variance == 0.0 {
+            return self;
+        }
+        let pi = 1.0 / (1.0 / self.pi + variance);
+        Self {
+            pi,
+            tau: pi * self.tau / self.pi,
+        }
+    }
+}
+
+/// One player in one game, in time order per player.
+#[derive(Debug, Clone, Copy)]
+struct Appearance {
+    game: usize,
+    team: usize,
+    position: usize,
+    forward: Message,
+    backward: Message,
+    likelihood: Message,
+}
+
+/// Forward-backward smoothing of a whole history in the style of TrueSkill Through Time. Each
+/// game is summarised per player by the Gaussian message that `rate` multiplies into the rating,
+/// and sweeps repeat until the messages settle. With a single sweep and no later games this is
+/// exactly the online filter.
+pub(crate) fn smooth<K: Ord + Clone>(
+    history: &[TimedResult<K>],
+    policy: &SmoothPolicy,
+    prior: &Rating,
+    default_tau: f64,
+    rate: impl Fn(&GameResult) -> Result<Vec<Vec<Rating>>>,
+) -> Result<BTreeMap<K, Vec<SkillPoint>>> {
+    let tau = policy.tau.unwrap_or(default_tau);
+    if !tau.is_finite() || tau < 0.0 {
+        return Err(OpenSkillError::InvalidArgument(
+            "tau must be a finite non-negative number",
+        ));
+    }
+    let iterations = policy.iterations.unwrap_or(16).max(1);
+    let tolerance = policy.tolerance.unwrap_or(1e-6);
+
+    let mut order: Vec<usize> = (0..history.len()).collect();
+    order.sort_by(|a, b| history[*a].time.total_cmp(&history[*b].time));
+    let mut players: BTreeMap<K, Vec<Appearance>> = BTreeMap::new();
+    for game in &order {
+        let result = &history[*game];
+        if !result.time.is_finite() {
+            return Err(OpenSkillError::InvalidArgument("time must be finite"));
+        }
+        if result.teams.len() < 2 {
+            return Err(OpenSkillError::InvalidTeamCount(
+                "game must have atleast 2 teams",
+            ));
+        }
+        validate_match(&result.teams, &result.ranks)?;
+        for (team, ids) in result.teams.iter().enumerate() {
+            for (position, id) in ids.iter().enumerate() {
+                players.entry(id.clone()).or_default().push(Appearance {
+                    game: *game,
+                    team,
+                    position,
+                    forward: Message::default(),
+                    backward: Message::default(),
+                    likelihood: Message::default(),
+                });
+            }
+        }
+    }
+
+    // where each (game, team, position) sits in `players`
+    let mut slots: BTreeMap<(usize, usize, usize), (K, usize)> = BTreeMap::new();
+    for (id, appearances) in &players {
+        for (k, it) in appearances.iter().enumerate() {
+            slots.insert((it.game, it.team, it.position), (id.clone(), k));
+        }
+    }
+
+    let prior = Message::from_rating(prior);
+    let drift = |from: usize, to: usize| tau.powi(2) * (history[to].time - history[from].time);
+    for _ in 0..iterations {
+        let mut change: f64 = 0.0;
+        for game in &order {
+            let result = &history[*game];
+            let mut cavities = Vec::with_capacity(result.teams.len());
+            for (team, ids) in result.teams.iter().enumerate() {
+                let mut ratings = Vec::with_capacity(ids.len());
+                for position in 0..ids.len() {
+                    let (id, k) = &slots[&(*game, team, position)];
+                    let appearances = players.get_mut(id).unwrap();
+                    appearances[*k].forward = match k.checked_sub(1) {
+                        None => prior,
+                        Some(previous) => {
+                            let before = &appearances[previous];
+                            before
+                                .forward
+                                .mul(before.likelihood)
+                                .drift(drift(before.game, *game))
+                        }
+                    };
+                    let it = &appearances[*k];
+                    ratings.push(it.forward.mul(it.backward).rating());
+                }
+                cavities.push(ratings);
+            }
+
+            let rated = rate(
+                &GameResult::new(cavities.clone(), result.ranks.clone()).weight(result.weight),
+            )?;
+            for (team, ratings) in rated.iter().enumerate() {
+                for (position, posterior) in ratings.iter().enumerate() {
+                    let (id, k) = &slots[&(*game, team, position)];
+                    let it = &mut players.get_mut(id).unwrap()[*k];
+                    let old = it.forward.mul(it.backward).mul(it.likelihood).rating();
+                    it.likelihood = Message::from_rating(posterior)
+                        .div(Message::from_rating(&cavities[team][position]));
+                    change = change
+                        .max((posterior.mu - old.mu).abs())
+                        .max((posterior.sigma - old.sigma).abs());
+                }
+            }
+        }
+
+        for appearances in players.values_mut() {
+            for k in (0..appearances.len().saturating_sub(1)).rev() {
+                let after = appearances[k + 1];
+                appearances[k].backward = after
+                    .backward
+                    .mul(after.likelihood)
+                    .drift(drift(appearances[k].game, after.game));
+            }
+        }
+
+        if change <= tolerance {
+            break;
+        }
+    }
+
+    Ok(players
+        .into_iter()
+        .map(|(id, appearances)| {
+            let points = appearances
+                .iter()
+                .map(|it| SkillPoint {
+                    time: history[it.game].time,
+                    rating: it.forward.mul(it.likelihood).mul(it.backward).rating(),
+                })
+                .collect();
+            (id, points)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::env_builder::EnvBuilder;
+    use approx::relative_eq;
+
+    fn game(time: f64, teams: &[&[&'static str]], ranks: Vec<usize>) -> TimedResult<&'static str> {
+        TimedResult::new(time, teams.iter().map(|it| it.to_vec()).collect(), ranks)
+    }
+
+    #[test]
+    fn single_game_matches_rate() {
+        let env = EnvBuilder::default().build();
+        let got = env
+            .smooth(
+                &[game(0.0, &[&["a", "b"], &["c"]], vec![1, 2])],
+                SmoothPolicy::default(),
+            )
+            .unwrap();
+        let want = env
+            .rate(&GameResult::new(
+                vec![vec![env.new_rating(); 2], vec![env.new_rating()]],
+                vec![1, 2],
+            ))
+            .unwrap();
+        for (id, rating) in [("a", &want[0][0]), ("c", &want[1][0])] {
+            let point = &got[id][0];
+            assert!(relative_eq!(point.rating.mu, rating.mu, epsilon = 1e-9));
+            assert!(relative_eq!(
+                point.rating.sigma,
+                rating.sigma,
+                epsilon = 1e-9
+            ));
+        }
+    }
+
+    #[test]
+    fn static_skill_gives_flat_trajectories() {
+        let env = EnvBuilder::default().build();
+        let history = [
+            game(0.0, &[&["a"], &["b"]], vec![1, 2]),
+            game(1.0, &[&["b"], &["c"]], vec![1, 2]),
+            game(2.0, &[&["c"], &["a"]], vec![1, 2]),
+            game(3.0, &[&["a"], &["b"]], vec![1, 2]),
+        ];
+        let got = env
+            .smooth(&history, SmoothPolicy::default().tau(0.0).iterations(100))
+            .unwrap();
+        for points in got.values() {
+            for it in points {
+                assert!(relative_eq!(
+                    it.rating.mu,
+                    points[0].rating.mu,
+                    epsilon = 1e-5
+                ));
+                assert!(relative_eq!(
+                    it.rating.sigma,
+                    points[0].rating.sigma,
+                    epsilon = 1e-5
+                ));
+            }
+        }
+        assert!(got["a"][0].rating.mu > got["b"][0].rating.mu);
+    }
+
+    #[test]
+    fn later_games_revise_early_ratings() {
+        // "a" beats "b" once; "b" then turns out to be strong, which online rating never
+        // carries back to "a"
+        let env = EnvBuilder::default().build();
+        let mut history = vec![game(0.0, &[&["a"], &["b"]], vec![1, 2])];
+        for time in 1..10 {
+            history.push(game(time as f64, &[&["b"], &["c"]], vec![1, 2]));
+        }
+        let filtered = env
+            .rate(&GameResult::new(
+                vec![vec![env.new_rating()], vec![env.new_rating()]],
+                vec![1, 2],
+            ))
+            .unwrap();
+        let got = env.smooth(&history, SmoothPolicy::default()).unwrap();
+        assert!(got["a"][0].rating.mu > filtered[0][0].mu + 1.0);
+        assert!(got["b"][0].rating.mu > filtered[1][0].mu + 1.0);
+        assert_eq!(got["b"].len(), 10);
+        assert_eq!(got["b"][9].time, 9.0);
+    }
+
+    #[test]
+    fn history_order_does_not_matter() {
+        let env = EnvBuilder::default().build();
+        let history = [
+            game(2.0, &[&["c"], &["a"]], vec![1, 2]),
+            game(0.0, &[&["a"], &["b"]], vec![1, 2]),
+            game(1.0, &[&["b"], &["c"]], vec![2, 1]),
+        ];
+        let mut sorted = history.to_vec();
+        sorted.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let policy = SmoothPolicy::default();
+        let a = env.smooth(&history, policy.clone()).unwrap();
+        let b = env.smooth(&sorted, policy).unwrap();
+        for (id, points) in &a {
+            for (x, y) in points.iter().zip(&b[id]) {
+                assert_eq!(x.time, y.time);
+                assert_eq!(x.rating.mu, y.rating.mu);
+                assert_eq!(x.rating.sigma, y.rating.sigma);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_history_is_error() {
+        let env = EnvBuilder::default().build();
+        let cases = [
+            game(0.0, &[&["a"]], vec![1]),
+            game(0.0, &[&["a"], &["b"]], vec![1]),
+            game(0.0, &[&["a"], &["a"]], vec![1, 2]),
+            game(f64::NAN, &[&["a"], &["b"]], vec![1, 2]),
+            game(0.0, &[&["a"], &[]], vec![1, 2]),
+        ];
+        for case in cases {
+            assert!(env.smooth(&[case], SmoothPolicy::default()).is_err());
+        }
+        assert!(env
+            .smooth::<&str>(&[], SmoothPolicy::default().tau(-1.0))
+            .is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{
+    error::{OpenSkillError, Result},
+    rating::{OrdinalFunc, Rating},
+};
+use alloc::vec::Vec;
+use num_traits::Float;
+
+#[derive(Debug, Clone, Default)]
+pub struct SoftResetPolicy {
+    fraction: Option<f64>,
+    max_ordinal: Option<f64>,
+    min_sigma: Option<f64>,
+}
+
+impl SoftResetPolicy {
+    /// Moves every `mu` this fraction of the way toward the default mu (0.0 keeps it, 1.0 resets it).
+    pub fn fraction(mut self, fraction: f64) -> Self {
+        self.fraction = Some(fraction);
+        self
+    }
+
+    /// Lowers `mu` of anyone whose ordinal is above `max_ordinal` so that it lands exactly on it.
+    pub fn max_ordinal(mut self, max_ordinal: f64) -> Self {
+        self.max_ordinal = Some(max_ordinal);
+        self
+    }
+
+    /// Raises `sigma` to at least `min_sigma`.
+    pub fn min_sigma(mut self, min_sigma: f64) -> Self {
+        self.min_sigma = Some(min_sigma);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(fraction) = self.fraction {
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err(OpenSkillError::InvalidArgument(
+                    "fraction must be between 0.0 and 1.0",
+                ));
+            }
+        }
+        if let Some(min_sigma) = self.min_sigma {
+            if !min_sigma.is_finite() || min_sigma < 0.0 {
+                return Err(OpenSkillError::InvalidArgument(
+                    "min_sigma must be a finite non-negative number",
+                ));
+            }
+        }
+        if let Some(max_ordinal) = self.max_ordinal {
+            if !max_ordinal.is_finite() {
+                return Err(OpenSkillError::InvalidArgument(
+                    "max_ordinal must be finite",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingSummary {
+    pub count: usize,
+    pub mean_mu: f64,
+    pub std_dev_mu: f64,
+    pub mean_sigma: f64,
+    pub min_ordinal: f64,
+    pub max_ordinal: f64,
+    pub mean_ordinal: f64,
+}
+
+impl RatingSummary {
+    fn new(ratings: &[Rating], ordinal: impl Fn(&Rating) -> f64) -> Self {
+        let count = ratings.len();
+        let n = count as f64;
+        let mean_mu = ratings.iter().map(|it| it.mu).sum::<f64>() / n;
+        let std_dev_mu = (ratings
+            .iter()
+            .map(|it| (it.mu - mean_mu).powi(2))
+            .sum::<f64>()
+            / n)
+            .sqrt();
+        let mean_sigma = ratings.iter().map(|it| it.sigma).sum::<f64>() / n;
+        let ordinals: Vec<f64> = ratings.iter().map(ordinal).collect();
+        Self {
+            count,
+            mean_mu,
+            std_dev_mu,
+            mean_sigma,
+            min_ordinal: ordinals.iter().cloned().fold(f64::INFINITY, f64::min),
+            max_ordinal: ordinals.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            mean_ordinal: ordinals.iter().sum::<f64>() / n,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SoftReset<F = f64> {
+    pub ratings: Vec<Rating<F>>,
+    pub before: RatingSummary,
+    pub after: RatingSummary,
+}
+
+impl<F: Float> SoftReset<F> {
+    pub fn cast<G: Float>(&self) -> SoftReset<G> {
+        SoftReset {
+            ratings: self.ratings.iter().map(Rating::cast).collect(),
+            before: self.before.clone(),
+            after: self.after.clone(),
+        }
+    }
+}
+
+/// Applies `policy` in order: pull toward `default_mu`, raise sigma, then cap the ordinal.
+/// The cap divides the ordinal overshoot by the slope of the ordinal in `mu` at the player's
+/// sigma, which is exact for any ordinal linear in `mu`.
+pub fn soft_reset(
+    ratings: &[Rating],
+    policy: &SoftResetPolicy,
+    default_mu: f64,
+    z: f64,
+    ordinal: OrdinalFunc,
+) -> Result<SoftReset> {
+    if ratings.is_empty() {
+        return Err(OpenSkillError::InvalidArgument(
+            "ratings must contain atleast 1 player",
+        ));
+    }
+    policy.validate()?;
+
+    let ordinal = |rating: &Rating| ordinal(rating, z);
+    let reset: Vec<Rating> = ratings
+        .iter()
+        .map(|it| {
+            let mut mu = it.mu;
+            let mut sigma = it.sigma;
+            if let Some(fraction) = policy.fraction {
+                mu += (default_mu - mu) * fraction;
+            }
+            if let Some(min_sigma) = policy.min_sigma {
+                sigma = sigma.max(min_sigma);
+            }
+            if let Some(max_ordinal) = policy.max_ordinal {
+                let at = ordinal(&Rating::new(mu, sigma));
+                let over = at - max_ordinal;
+                if over > 0.0 {
+                    let slope = ordinal(&Rating::new(mu + 1.0, sigma)) - at;
+                    if slope.is_nan() || slope <= 0.0 {
+                        return Err(OpenSkillError::InvalidArgument(
+                            "max_ordinal needs an ordinal that increases with mu",
+                        ));
+                    }
+                    mu -= over / slope;
+                }
+            }
+            Ok(Rating::new(mu, sigma))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(SoftReset {
+        before: RatingSummary::new(ratings, ordinal),
+        after: RatingSummary::new(&reset, ordinal),
+        ratings: reset,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constant::{DEFAULT_MU, DEFAULT_Z};
+    use crate::rating::default_ordinal;
+    use approx::relative_eq;
+
+    fn reset(ratings: &[Rating], policy: SoftResetPolicy) -> Result<SoftReset> {
+        soft_reset(ratings, &policy, DEFAULT_MU, DEFAULT_Z, default_ordinal)
+    }
+
+    #[test]
+    fn empty_ratings_is_error() {
+        let got = reset(&[], SoftResetPolicy::default());
+        assert_eq!(
+            got.unwrap_err(),
+            OpenSkillError::InvalidArgument("ratings must contain atleast 1 player")
+        );
+    }
+
+    #[test]
+    fn invalid_fraction_is_error() {
+        let got = reset(
+            &[Rating::default()],
+            SoftResetPolicy::default().fraction(1.5),
+        );
+        assert!(got.is_err());
+    }
+
+    #[test]
+    fn non_finite_bounds_are_errors() {
+        for min_sigma in [f64::NAN, f64::INFINITY, -1.0] {
+            let got = reset(
+                &[Rating::default()],
+                SoftResetPolicy::default().min_sigma(min_sigma),
+            );
+            assert_eq!(
+                got.unwrap_err(),
+                OpenSkillError::InvalidArgument("min_sigma must be a finite non-negative number")
+            );
+        }
+        for max_ordinal in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let got = reset(
+                &[Rating::default()],
+                SoftResetPolicy::default().max_ordinal(max_ordinal),
+            );
+            assert_eq!(
+                got.unwrap_err(),
+                OpenSkillError::InvalidArgument("max_ordinal must be finite")
+            );
+        }
+    }
+
+    #[test]
+    fn empty_policy_keeps_ratings() {
+        let ratings = vec![Rating::new(30.0, 2.0), Rating::new(20.0, 4.0)];
+        let got = reset(&ratings, SoftResetPolicy::default()).unwrap();
+        assert_eq!(got.before, got.after);
+        assert!(relative_eq!(got.ratings[0].mu, 30.0));
+        assert!(relative_eq!(got.ratings[1].sigma, 4.0));
+    }
+
+    #[test]
+    fn fraction_pulls_toward_default_mu() {
+        let ratings = vec![Rating::new(35.0, 2.0), Rating::new(15.0, 2.0)];
+        let got = reset(&ratings, SoftResetPolicy::default().fraction(0.5)).unwrap();
+        assert!(relative_eq!(got.ratings[0].mu, 30.0));
+        assert!(relative_eq!(got.ratings[1].mu, 20.0));
+        assert!(relative_eq!(got.before.std_dev_mu, 10.0));
+        assert!(relative_eq!(got.after.std_dev_mu, 5.0));
+        assert!(relative_eq!(got.after.mean_mu, 25.0));
+    }
+
+    #[test]
+    fn min_sigma_and_max_ordinal() {
+        let ratings = vec![Rating::new(50.0, 1.0), Rating::new(20.0, 6.0)];
+        let policy = SoftResetPolicy::default().min_sigma(3.0).max_ordinal(30.0);
+        let got = reset(&ratings, policy).unwrap();
+        assert!(relative_eq!(got.ratings[0].sigma, 3.0));
+        assert!(relative_eq!(got.ratings[0].mu, 39.0));
+        assert!(relative_eq!(got.ratings[1].mu, 20.0));
+        assert!(relative_eq!(got.ratings[1].sigma, 6.0));
+        assert!(relative_eq!(got.after.max_ordinal, 30.0));
+        assert!(relative_eq!(got.before.max_ordinal, 47.0));
+    }
+
+    #[test]
+    fn max_ordinal_uses_the_slope_of_the_ordinal() {
+        fn steep(rating: &Rating, z: f64) -> f64 {
+            2.0 * rating.mu - z * rating.sigma
+        }
+        fn flat(rating: &Rating, _: f64) -> f64 {
+            rating.sigma
+        }
+        let ratings = vec![Rating::new(50.0, 1.0)];
+        let policy = SoftResetPolicy::default().max_ordinal(30.0);
+        let got = soft_reset(&ratings, &policy, DEFAULT_MU, DEFAULT_Z, steep).unwrap();
+        assert!(relative_eq!(got.ratings[0].mu, 16.5));
+        assert!(relative_eq!(got.after.max_ordinal, 30.0));
+
+        let policy = SoftResetPolicy::default().max_ordinal(0.0);
+        assert_eq!(
+            soft_reset(&ratings, &policy, DEFAULT_MU, DEFAULT_Z, flat).unwrap_err(),
+            OpenSkillError::InvalidArgument("max_ordinal needs an ordinal that increases with mu")
+        );
+    }
+}
@@ -0,0 +1,330 @@
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use crate::{
+    env::Env,
+    error::OpenSkillError,
+    rating::{GameResult, Rating},
+    validate::validate_match,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::convert::Infallible;
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub struct PlayerRow {
+    pub id: String,
+    pub rating: Rating,
+}
+
+/// A match as applied to storage: player ids per team plus the ranks of the teams.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchRecord {
+    pub teams: Vec<Vec<String>>,
+    pub ranks: Vec<usize>,
+    pub weight: f64,
+}
+
+impl MatchRecord {
+    pub fn new(teams: Vec<Vec<String>>, ranks: Vec<usize>) -> Self {
+        Self {
+            teams,
+            ranks,
+            weight: 1.0,
+        }
+    }
+
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum StorageError<E> {
+    #[error(transparent)]
+    Rating(#[from] OpenSkillError),
+    #[error("Storage backend {0}")]
+    Backend(E),
+}
+
+/// Reads and writes of one `Storage::apply` call, all inside the same backend transaction.
+pub trait Transaction {
+    type Error;
+
+    fn load(&mut self, id: &str) -> Result<Option<Rating>, Self::Error>;
+
+    fn save_match(
+        &mut self,
+        record: &MatchRecord,
+        ratings: &[PlayerRow],
+    ) -> Result<u64, Self::Error>;
+}
+
+pub trait Storage {
+    type Error;
+
+    fn load(&self, id: &str) -> Result<Option<Rating>, Self::Error>;
+
+    /// Runs `f` in a single transaction that commits when it returns `Ok` and rolls back
+    /// otherwise. No other writer can change what `f` loads before the commit, so a
+    /// read-rate-write in `f` never loses a concurrent update.
+    fn apply<T>(
+        &mut self,
+        f: impl FnOnce(
+            &mut dyn Transaction<Error = Self::Error>,
+        ) -> Result<T, StorageError<Self::Error>>,
+    ) -> Result<T, StorageError<Self::Error>>;
+
+    /// Records `record` together with the new rating of every player in it, atomically:
+    /// after an error neither the match nor any rating is stored. Returns the match id.
+    fn save_match(
+        &mut self,
+        record: &MatchRecord,
+        ratings: &[PlayerRow],
+    ) -> Result<u64, Self::Error>;
+
+    /// Every applied match in the order it was saved.
+    fn matches(&self) -> Result<Vec<(u64, MatchRecord)>, Self::Error>;
+
+    /// Inserts or replaces all `rows` atomically.
+    fn import(&mut self, rows: &[PlayerRow]) -> Result<(), Self::Error>;
+
+    /// Every stored player, ordered by id.
+    fn export(&self) -> Result<Vec<PlayerRow>, Self::Error>;
+}
+
+/// Rates `record` with the stored ratings (unknown players start at `env.new_rating()`) and
+/// saves the match and all updated ratings, all in one `Storage::apply` transaction.
+pub fn apply_match<S: Storage>(
+    env: &Env,
+    storage: &mut S,
+    record: &MatchRecord,
+) -> Result<Vec<Vec<Rating>>, StorageError<S::Error>> {
+    validate_match(&record.teams, &record.ranks)?;
+
+    storage.apply(|tx| {
+        let mut teams = Vec::with_capacity(record.teams.len());
+        for team in &record.teams {
+            let mut ratings = Vec::with_capacity(team.len());
+            for id in team {
+                let rating = tx.load(id).map_err(StorageError::Backend)?;
+                ratings.push(rating.unwrap_or_else(|| env.new_rating()));
+            }
+            teams.push(ratings);
+        }
+        let rated =
+            env.rate(&GameResult::new(teams, record.ranks.clone()).weight(record.weight))?;
+
+        let rows: Vec<PlayerRow> = record
+            .teams
+            .iter()
+            .flatten()
+            .zip(rated.iter().flatten())
+            .map(|(id, rating)| PlayerRow {
+                id: id.clone(),
+                rating: rating.clone(),
+            })
+            .collect();
+        tx.save_match(record, &rows)
+            .map_err(StorageError::Backend)?;
+        Ok(rated)
+    })
+}
+
+/// Storage kept in memory, mostly useful for tests and short lived tools.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    players: BTreeMap<String, Rating>,
+    matches: Vec<MatchRecord>,
+}
+
+/// Writes of a `MemoryStorage::apply` call, merged into the storage on commit.
+struct MemoryTransaction<'a> {
+    storage: &'a MemoryStorage,
+    players: BTreeMap<String, Rating>,
+    matches: Vec<MatchRecord>,
+}
+
+impl Transaction for MemoryTransaction<'_> {
+    type Error = Infallible;
+
+    fn load(&mut self, id: &str) -> Result<Option<Rating>, Self::Error> {
+        match self.players.get(id) {
+            Some(rating) => Ok(Some(rating.clone())),
+            None => self.storage.load(id),
+        }
+    }
+
+    fn save_match(
+        &mut self,
+        record: &MatchRecord,
+        ratings: &[PlayerRow],
+    ) -> Result<u64, Self::Error> {
+        for row in ratings {
+            self.players.insert(row.id.clone(), row.rating.clone());
+        }
+        self.matches.push(record.clone());
+        Ok((self.storage.matches.len() + self.matches.len()) as u64)
+    }
+}
+
+impl Storage for MemoryStorage {
+    type Error = Infallible;
+
+    fn load(&self, id: &str) -> Result<Option<Rating>, Self::Error> {
+        Ok(self.players.get(id).cloned())
+    }
+
+    fn apply<T>(
+        &mut self,
+        f: impl FnOnce(
+            &mut dyn Transaction<Error = Self::Error>,
+        ) -> Result<T, StorageError<Self::Error>>,
+    ) -> Result<T, StorageError<Self::Error>> {
+        let mut tx = MemoryTransaction {
+            storage: self,
+            players: BTreeMap::new(),
+            matches: Vec::new(),
+        };
+        let value = f(&mut tx)?;
+        let MemoryTransaction {
+            players, matches, ..
+        } = tx;
+        self.players.extend(players);
+        self.matches.extend(matches);
+        Ok(value)
+    }
+
+    fn save_match(
+        &mut self,
+        record: &MatchRecord,
+        ratings: &[PlayerRow],
+    ) -> Result<u64, Self::Error> {
+        self.import(ratings)?;
+        self.matches.push(record.clone());
+        Ok(self.matches.len() as u64)
+    }
+
+    fn matches(&self) -> Result<Vec<(u64, MatchRecord)>, Self::Error> {
+        Ok((1..).zip(self.matches.iter().cloned()).collect())
+    }
+
+    fn import(&mut self, rows: &[PlayerRow]) -> Result<(), Self::Error> {
+        for row in rows {
+            self.players.insert(row.id.clone(), row.rating.clone());
+        }
+        Ok(())
+    }
+
+    fn export(&self) -> Result<Vec<PlayerRow>, Self::Error> {
+        Ok(self
+            .players
+            .iter()
+            .map(|(id, rating)| PlayerRow {
+                id: id.clone(),
+                rating: rating.clone(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::env_builder::EnvBuilder;
+    use alloc::{string::ToString, vec};
+
+    pub(crate) fn record(teams: &[&[&str]], ranks: Vec<usize>) -> MatchRecord {
+        let teams = teams
+            .iter()
+            .map(|team| team.iter().map(|id| id.to_string()).collect())
+            .collect();
+        MatchRecord::new(teams, ranks)
+    }
+
+    /// Checks the contract every `Storage` implementation has to meet.
+    pub(crate) fn storage_contract<S: Storage>(storage: &mut S)
+    where
+        S::Error: core::fmt::Debug,
+    {
+        let env = EnvBuilder::default().build();
+        let first = record(&[&["alice"], &["bob", "carol"]], vec![1, 2]);
+        let rated = apply_match(&env, storage, &first).unwrap();
+        let alice = storage.load("alice").unwrap().unwrap();
+        assert_eq!(alice.mu, rated[0][0].mu);
+        assert_eq!(alice.sigma, rated[0][0].sigma);
+        assert!(storage.load("dave").unwrap().is_none());
+
+        let second = record(&[&["alice"], &["dave"]], vec![2, 1]).weight(0.5);
+        apply_match(&env, storage, &second).unwrap();
+        let expected = env
+            .rate(
+                &GameResult::new(vec![vec![alice], vec![env.new_rating()]], vec![2, 1]).weight(0.5),
+            )
+            .unwrap();
+        assert_eq!(
+            storage.load("alice").unwrap().unwrap().mu,
+            expected[0][0].mu
+        );
+
+        let matches = storage.matches().unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(matches[0].0 < matches[1].0);
+        assert_eq!(matches[0].1, first);
+        assert_eq!(matches[1].1, second);
+
+        let ids: Vec<String> = storage
+            .export()
+            .unwrap()
+            .into_iter()
+            .map(|it| it.id)
+            .collect();
+        assert_eq!(ids, ["alice", "bob", "carol", "dave"]);
+
+        storage
+            .import(&[PlayerRow {
+                id: "bob".to_string(),
+                rating: Rating::new(40.0, 1.0),
+            }])
+            .unwrap();
+        assert_eq!(storage.load("bob").unwrap().unwrap().mu, 40.0);
+        assert_eq!(storage.export().unwrap().len(), 4);
+
+        // a failing `apply` rolls back what it already wrote
+        let third = record(&[&["bob"], &["erin"]], vec![1, 2]);
+        let rows = [PlayerRow {
+            id: "erin".to_string(),
+            rating: Rating::new(1.0, 1.0),
+        }];
+        let failed: Result<(), _> = storage.apply(|tx| {
+            tx.save_match(&third, &rows)
+                .map_err(StorageError::Backend)?;
+            assert_eq!(tx.load("erin").unwrap().unwrap().mu, 1.0);
+            Err(StorageError::Rating(OpenSkillError::EmptyTeams))
+        });
+        assert!(failed.is_err());
+        assert!(storage.load("erin").unwrap().is_none());
+        assert_eq!(storage.matches().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn memory_storage_meets_contract() {
+        storage_contract(&mut MemoryStorage::default());
+    }
+
+    #[test]
+    fn invalid_match_is_not_stored() {
+        let env = EnvBuilder::default().build();
+        let mut storage = MemoryStorage::default();
+        let twice = record(&[&["alice"], &["alice"]], vec![1, 2]);
+        assert!(matches!(
+            apply_match(&env, &mut storage, &twice),
+            Err(StorageError::Rating(_))
+        ));
+        let empty = record(&[&["alice"], &[]], vec![1, 2]);
+        assert!(apply_match(&env, &mut storage, &empty).is_err());
+        assert!(storage.export().unwrap().is_empty());
+        assert!(storage.matches().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,329 @@
+use super::{MatchRecord, PlayerRow, Storage, StorageError, Transaction};
+use crate::rating::Rating;
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use std::path::Path;
+
+const CREATE_TABLES: &str = "
+    CREATE TABLE players (
+        id TEXT PRIMARY KEY,
+        mu REAL NOT NULL,
+        sigma REAL NOT NULL
+    );
+    CREATE TABLE matches (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        weight REAL NOT NULL
+    );
+    CREATE TABLE match_players (
+        match_id INTEGER NOT NULL REFERENCES matches(id),
+        team INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        rank INTEGER NOT NULL,
+        player_id TEXT NOT NULL REFERENCES players(id),
+        PRIMARY KEY (match_id, team, position)
+    );
+";
+
+/// Schema steps, applied in order; `PRAGMA user_version` holds how many already ran.
+/// Only ever append to this list.
+const MIGRATIONS: &[&str] = &[CREATE_TABLES];
+
+pub struct SqliteStorage {
+    conn: Connection,
+}
+
+impl SqliteStorage {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    /// Wraps an existing connection and migrates it to the current schema.
+    pub fn new(conn: Connection) -> rusqlite::Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let mut storage = Self { conn };
+        storage.migrate()?;
+        Ok(storage)
+    }
+
+    pub fn schema_version(&self) -> rusqlite::Result<usize> {
+        self.conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+    }
+
+    fn migrate(&mut self) -> rusqlite::Result<()> {
+        let version = self.schema_version()?;
+        for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", i + 1)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+}
+
+fn load(conn: &Connection, id: &str) -> rusqlite::Result<Option<Rating>> {
+    conn.prepare_cached("SELECT mu, sigma FROM players WHERE id = ?1")?
+        .query_row([id], |row| Ok(Rating::new(row.get(0)?, row.get(1)?)))
+        .optional()
+}
+
+fn insert_match(
+    conn: &Connection,
+    record: &MatchRecord,
+    ratings: &[PlayerRow],
+) -> rusqlite::Result<u64> {
+    upsert(conn, ratings)?;
+    conn.execute("INSERT INTO matches (weight) VALUES (?1)", [record.weight])?;
+    let match_id = conn.last_insert_rowid();
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO match_players (match_id, team, position, rank, player_id)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for (team, (ids, rank)) in record.teams.iter().zip(&record.ranks).enumerate() {
+        for (position, id) in ids.iter().enumerate() {
+            stmt.execute(params![match_id, team, position, rank, id])?;
+        }
+    }
+    Ok(match_id as u64)
+}
+
+/// An open `BEGIN IMMEDIATE` transaction: it holds the write lock from the first read on.
+struct SqliteTransaction<'a>(&'a Connection);
+
+impl Transaction for SqliteTransaction<'_> {
+    type Error = rusqlite::Error;
+
+    fn load(&mut self, id: &str) -> rusqlite::Result<Option<Rating>> {
+        load(self.0, id)
+    }
+
+    fn save_match(&mut self, record: &MatchRecord, ratings: &[PlayerRow]) -> rusqlite::Result<u64> {
+        insert_match(self.0, record, ratings)
+    }
+}
+
+fn upsert(conn: &Connection, rows: &[PlayerRow]) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO players (id, mu, sigma) VALUES (?1, ?2, ?3)
+         ON CONFLICT(id) DO UPDATE SET mu = excluded.mu, sigma = excluded.sigma",
+    )?;
+    for row in rows {
+        stmt.execute(params![row.id, row.rating.mu, row.rating.sigma])?;
+    }
+    Ok(())
+}
+
+impl Storage for SqliteStorage {
+    type Error = rusqlite::Error;
+
+    fn load(&self, id: &str) -> rusqlite::Result<Option<Rating>> {
+        load(&self.conn, id)
+    }
+
+    fn apply<T>(
+        &mut self,
+        f: impl FnOnce(
+            &mut dyn Transaction<Error = Self::Error>,
+        ) -> Result<T, StorageError<Self::Error>>,
+    ) -> Result<T, StorageError<Self::Error>> {
+        // dropping `tx` on any early return rolls everything back
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(StorageError::Backend)?;
+        let value = f(&mut SqliteTransaction(&tx))?;
+        tx.commit().map_err(StorageError::Backend)?;
+        Ok(value)
+    }
+
+    fn save_match(&mut self, record: &MatchRecord, ratings: &[PlayerRow]) -> rusqlite::Result<u64> {
+        let tx = self.conn.transaction()?;
+        let match_id = insert_match(&tx, record, ratings)?;
+        tx.commit()?;
+        Ok(match_id)
+    }
+
+    fn matches(&self) -> rusqlite::Result<Vec<(u64, MatchRecord)>> {
+        let mut matches: Vec<(u64, MatchRecord)> = self
+            .conn
+            .prepare("SELECT id, weight FROM matches ORDER BY id")?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)? as u64,
+                    MatchRecord::new(vec![], vec![]).weight(row.get(1)?),
+                ))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT match_id, team, rank, player_id FROM match_players
+             ORDER BY match_id, team, position",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut current = matches.iter_mut().peekable();
+        while let Some(row) = rows.next()? {
+            let match_id = row.get::<_, i64>(0)? as u64;
+            let team: usize = row.get(1)?;
+            while current.next_if(|(id, _)| *id < match_id).is_some() {}
+            let Some((_, record)) = current.peek_mut() else {
+                break;
+            };
+            if record.teams.len() <= team {
+                record.teams.resize(team + 1, vec![]);
+                record.ranks.resize(team + 1, 0);
+            }
+            record.ranks[team] = row.get(2)?;
+            record.teams[team].push(row.get(3)?);
+        }
+        Ok(matches)
+    }
+
+    fn import(&mut self, rows: &[PlayerRow]) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        upsert(&tx, rows)?;
+        tx.commit()
+    }
+
+    fn export(&self) -> rusqlite::Result<Vec<PlayerRow>> {
+        self.conn
+            .prepare("SELECT id, mu, sigma FROM players ORDER BY id")?
+            .query_map([], |row| {
+                Ok(PlayerRow {
+                    id: row.get(0)?,
+                    rating: Rating::new(row.get(1)?, row.get(2)?),
+                })
+            })?
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        env_builder::EnvBuilder,
+        rating::GameResult,
+        storage::{
+            apply_match,
+            test::{record, storage_contract},
+            MemoryStorage,
+        },
+    };
+    use std::{sync::mpsc, thread, time::Duration};
+
+    #[test]
+    fn sqlite_storage_meets_contract() {
+        storage_contract(&mut SqliteStorage::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn failed_write_leaves_no_partial_match() {
+        let env = EnvBuilder::default().build();
+        let mut storage = SqliteStorage::open_in_memory().unwrap();
+        apply_match(
+            &env,
+            &mut storage,
+            &record(&[&["alice"], &["bob"]], vec![1, 2]),
+        )
+        .unwrap();
+        let ratings = |storage: &SqliteStorage| -> Vec<(String, f64, f64)> {
+            let rows = storage.export().unwrap().into_iter();
+            rows.map(|it| (it.id, it.rating.mu, it.rating.sigma))
+                .collect()
+        };
+        let before = ratings(&storage);
+
+        // NaN is stored as NULL by SQLite, so the second row violates NOT NULL mid-match
+        let rows = [
+            PlayerRow {
+                id: "alice".to_string(),
+                rating: Rating::new(99.0, 1.0),
+            },
+            PlayerRow {
+                id: "bob".to_string(),
+                rating: Rating::new(f64::NAN, 1.0),
+            },
+        ];
+        let game = record(&[&["alice"], &["bob"]], vec![1, 2]);
+        assert!(storage.save_match(&game, &rows).is_err());
+        assert_eq!(ratings(&storage), before);
+        assert_eq!(storage.matches().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn concurrent_writers_do_not_lose_updates() {
+        let path = std::env::temp_dir().join(format!(
+            "openskill-concurrent-{}.sqlite",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let env = EnvBuilder::default().build();
+        let mut first = SqliteStorage::open(&path).unwrap();
+        let mut second = SqliteStorage::open(&path).unwrap();
+        let game = record(&[&["alice"], &["bob"]], vec![1, 2]);
+        let other = record(&[&["alice"], &["carol"]], vec![1, 2]);
+
+        // the first writer has read alice when the second one starts its own update of her
+        let (loaded, wait) = mpsc::channel();
+        thread::scope(|scope| {
+            let writer = scope.spawn(|| {
+                first
+                    .apply(|tx| {
+                        let alice = tx.load("alice").unwrap().unwrap_or(env.new_rating());
+                        loaded.send(()).unwrap();
+                        thread::sleep(Duration::from_millis(100));
+                        let rated = env.rate(&GameResult::new(
+                            vec![vec![alice], vec![env.new_rating()]],
+                            vec![1, 2],
+                        ))?;
+                        let rows =
+                            [("alice", &rated[0][0]), ("bob", &rated[1][0])].map(|(id, rating)| {
+                                PlayerRow {
+                                    id: id.to_string(),
+                                    rating: rating.clone(),
+                                }
+                            });
+                        tx.save_match(&game, &rows).map_err(StorageError::Backend)
+                    })
+                    .unwrap()
+            });
+            wait.recv().unwrap();
+            apply_match(&env, &mut second, &other).unwrap();
+            writer.join().unwrap();
+        });
+
+        let mut sequential = MemoryStorage::default();
+        apply_match(&env, &mut sequential, &game).unwrap();
+        apply_match(&env, &mut sequential, &other).unwrap();
+        let want = sequential.load("alice").unwrap().unwrap();
+        let got = first.load("alice").unwrap().unwrap();
+        assert_eq!((got.mu, got.sigma), (want.mu, want.sigma));
+        assert_eq!(first.matches().unwrap().len(), 2);
+        drop((first, second));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reopening_keeps_data_and_schema() {
+        let path = std::env::temp_dir().join(format!("openskill-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let mut storage = SqliteStorage::open(&path).unwrap();
+            assert_eq!(storage.schema_version().unwrap(), MIGRATIONS.len());
+            storage
+                .import(&[PlayerRow {
+                    id: "alice".to_string(),
+                    rating: Rating::new(30.0, 2.0),
+                }])
+                .unwrap();
+        }
+        let storage = SqliteStorage::open(&path).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), MIGRATIONS.len());
+        assert_eq!(storage.load("alice").unwrap().unwrap().mu, 30.0);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,19 +1,21 @@
 use crate::rating::{Rating, TeamRating};
+use alloc::vec::Vec;
+use core::iter::zip;
 
-pub fn zip_without_self<T>(vector: &Vec<T>) -> impl Iterator<Item = (&T, Vec<T>)>
-where
-    T: Clone,
-{
-    vector.iter().enumerate().map(|(i, it)| {
-        (
-            it,
-            vector
-                .iter()
-                .enumerate()
-                .filter_map(|(j, jt)| if i != j { Some(jt.clone()) } else { None })
-                .collect::<Vec<T>>(),
-        )
-    })
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+pub(crate) fn member_shares(team: &TeamRating) -> Vec<f64> {
+    let free_sigma_sq = team.free_sigma_sq();
+    zip(&team.members, &team.anchored)
+        .map(|(it, anchored)| {
+            if *anchored || free_sigma_sq <= 0.0 {
+                0.0
+            } else {
+                it.sigma.powi(2) / free_sigma_sq
+            }
+        })
+        .collect()
 }
 
 pub(crate) fn update_team_rating(
@@ -22,16 +24,14 @@ pub(crate) fn update_team_rating(
     delta: f64,
     epsilon: f64,
 ) -> Vec<Rating> {
-    team.members
-        .iter()
-        .map(|it| {
-            let sigma_sq = it.sigma.powi(2);
+    zip(&team.members, member_shares(team))
+        .map(|(it, share)| {
+            if share == 0.0 {
+                return it.clone();
+            }
             Rating::new(
-                it.mu + (sigma_sq / team.sigma_sq) * omega,
-                it.sigma
-                    * (1.0 - (sigma_sq / team.sigma_sq) * delta)
-                        .max(epsilon)
-                        .sqrt(),
+                it.mu + share * omega,
+                it.sigma * (1.0 - share * delta).max(epsilon).sqrt(),
             )
         })
         .collect()
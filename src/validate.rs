@@ -1,10 +1,89 @@
-use crate::{error::OpenSkillError, rating::Rating};
+use crate::{
+    error::OpenSkillError,
+    rating::{GameResult, Rating},
+};
+use alloc::{collections::BTreeSet, vec::Vec};
 
-pub(crate) fn validate_team(teams: &Vec<Vec<Rating>>) -> Result<(), OpenSkillError> {
-    if teams.iter().filter(|team| team.len() < 1).count() >= 1 {
+pub(crate) fn validate_team(teams: &[Vec<Rating>]) -> Result<(), OpenSkillError> {
+    if teams.iter().filter(|team| team.is_empty()).count() >= 1 {
         return Err(OpenSkillError::InvalidTeamCount(
             "team must contain atleast 1 player",
         ));
     }
     Ok(())
 }
+
+pub(crate) fn validate_game_result(result: &GameResult) -> Result<(), OpenSkillError> {
+    for team in &result.teams {
+        if team.is_empty() {
+            return Err(OpenSkillError::EmptyTeams);
+        }
+    }
+    validate_lengths(result)?;
+    validate_anchored(result)?;
+    validate_weight(result)
+}
+
+/// A match of player ids: every player at most once and one rank per team.
+pub fn validate_match<K: Ord>(teams: &[Vec<K>], ranks: &[usize]) -> Result<(), OpenSkillError> {
+    validate_unique(
+        teams.iter().flatten(),
+        "a player can only appear once per match",
+    )?;
+    validate_ranks(ranks, teams.len())
+}
+
+/// Fails with `message` if any of `ids` appears twice.
+pub fn validate_unique<T: Ord>(
+    ids: impl IntoIterator<Item = T>,
+    message: &'static str,
+) -> Result<(), OpenSkillError> {
+    let mut seen = BTreeSet::new();
+    if !ids.into_iter().all(|id| seen.insert(id)) {
+        return Err(OpenSkillError::InvalidArgument(message));
+    }
+    Ok(())
+}
+
+fn validate_ranks(ranks: &[usize], teams: usize) -> Result<(), OpenSkillError> {
+    if ranks.len() != teams {
+        return Err(OpenSkillError::InvalidArgument(
+            "ranks must have one entry per team",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_lengths(result: &GameResult) -> Result<(), OpenSkillError> {
+    validate_ranks(&result.ranks, result.teams.len())?;
+    if !result.advantage.is_empty() && result.advantage.len() != result.teams.len() {
+        return Err(OpenSkillError::InvalidArgument(
+            "advantage must be empty or have one entry per team",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_anchored(result: &GameResult) -> Result<(), OpenSkillError> {
+    let out_of_range = result.anchored.iter().any(|(team, player)| {
+        result
+            .teams
+            .get(*team)
+            .is_none_or(|members| *player >= members.len())
+    });
+    if out_of_range {
+        return Err(OpenSkillError::InvalidArgument(
+            "anchored player is out of range",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_weight(result: &GameResult) -> Result<(), OpenSkillError> {
+    if !result.weight.is_finite() || result.weight < 0.0 {
+        return Err(OpenSkillError::InvalidArgument(
+            "weight must be a finite non-negative number",
+        ));
+    }
+    Ok(())
+}